@@ -1,3 +1,6 @@
+use sql_reverse::Lang;
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
+use sql_reverse::Target;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -9,18 +12,23 @@ pub struct ApplicationArguments {
 
 #[derive(Debug, StructOpt)]
 pub enum Command {
+    #[cfg(feature = "mysql")]
     /// Mysql OPTIONS
     #[structopt(name = "mysql")]
     Mysql(Mysql),
+    #[cfg(feature = "postgres")]
     /// Postgres OPTIONS
     #[structopt(name = "postgres")]
     Postgres(Postgres),
+    #[cfg(feature = "sqlite")]
     /// Sqlite OPTIONS
     #[structopt(name = "sqlite")]
     Sqlite(Sqlite),
+    #[cfg(feature = "clickhouse")]
     /// Clickhouse OPTIONS
     #[structopt(name = "clickhouse")]
     Clickhouse(Clickhouse),
+    #[cfg(feature = "tdengine")]
     /// TDengine OPTIONS
     #[structopt(name = "tdengine")]
     Tdengine(Tdengine),
@@ -29,6 +37,7 @@ pub enum Command {
     Export,
 }
 
+#[cfg(feature = "mysql")]
 #[derive(Debug, StructOpt)]
 pub struct Mysql {
     /// Input database config file to read, example: -f ./reverse.yml
@@ -37,17 +46,27 @@ pub struct Mysql {
     /// Input template path example: -p 'templates/*'
     #[structopt(short = "p", default_value = "templates/*")]
     pub template_path: String,
-    /// Input template name, example: -n mysql.tera
-    #[structopt(short = "n", default_value = "mysql.tera")]
-    pub template_name: String,
+    /// Input template name, example: -n mysql.tera, defaults to mysql.tera, <target>.tera with --target or <lang>.tera with --lang
+    #[structopt(short = "n")]
+    pub template_name: Option<String>,
     /// Custom field type, example: -c ./default.json
     #[structopt(short = "c", default_value = "")]
     pub custom_field_type: String,
-    /// Suffix of the generated file, example: -s rs
-    #[structopt(short = "s", default_value = "rs")]
-    pub suffix: String,
+    /// Suffix of the generated file, example: -s rs, defaults to the suffix of --lang
+    #[structopt(short = "s")]
+    pub suffix: Option<String>,
+    /// Exit with an error when any field type is not mapped, example: --strict
+    #[structopt(long = "strict")]
+    pub strict: bool,
+    /// Language of the generated code, rust, typescript, go, python, java or kotlin, example: --lang go
+    #[structopt(long = "lang", default_value = "rust")]
+    pub lang: Lang,
+    /// Library used by the generated Rust code, sqlx, diesel or seaorm, example: --target diesel
+    #[structopt(long = "target", default_value = "sqlx")]
+    pub target: Target,
 }
 
+#[cfg(feature = "postgres")]
 #[derive(Debug, StructOpt)]
 pub struct Postgres {
     /// Input database config file to read, example: -f ./reverse.yml
@@ -56,17 +75,27 @@ pub struct Postgres {
     /// Input template path example: -p 'templates/*'
     #[structopt(short = "p", default_value = "templates/*")]
     pub template_path: String,
-    /// Input template name, example: -n postgres.tera
-    #[structopt(short = "n", default_value = "postgres.tera")]
-    pub template_name: String,
+    /// Input template name, example: -n postgres.tera, defaults to postgres.tera, <target>.tera with --target or <lang>.tera with --lang
+    #[structopt(short = "n")]
+    pub template_name: Option<String>,
     /// Custom field type, example: -c ./default.json
     #[structopt(short = "c", default_value = "")]
     pub custom_field_type: String,
-    /// Suffix of the generated file, example: -s rs
-    #[structopt(short = "s", default_value = "rs")]
-    pub suffix: String,
+    /// Suffix of the generated file, example: -s rs, defaults to the suffix of --lang
+    #[structopt(short = "s")]
+    pub suffix: Option<String>,
+    /// Exit with an error when any field type is not mapped, example: --strict
+    #[structopt(long = "strict")]
+    pub strict: bool,
+    /// Language of the generated code, rust, typescript, go, python, java or kotlin, example: --lang go
+    #[structopt(long = "lang", default_value = "rust")]
+    pub lang: Lang,
+    /// Library used by the generated Rust code, sqlx, diesel or seaorm, example: --target diesel
+    #[structopt(long = "target", default_value = "sqlx")]
+    pub target: Target,
 }
 
+#[cfg(feature = "sqlite")]
 #[derive(Debug, StructOpt)]
 pub struct Sqlite {
     /// Input database config file to read, example: -f ./reverse.yml
@@ -75,17 +104,27 @@ pub struct Sqlite {
     /// Input template path example: -p 'templates/*'
     #[structopt(short = "p", default_value = "templates/*")]
     pub template_path: String,
-    /// Input template name, example: -n sqlite.tera
-    #[structopt(short = "n", default_value = "sqlite.tera")]
-    pub template_name: String,
+    /// Input template name, example: -n sqlite.tera, defaults to sqlite.tera, <target>.tera with --target or <lang>.tera with --lang
+    #[structopt(short = "n")]
+    pub template_name: Option<String>,
     /// Custom field type, example: -c ./default.json
     #[structopt(short = "c", default_value = "")]
     pub custom_field_type: String,
-    /// Suffix of the generated file, example: -s rs
-    #[structopt(short = "s", default_value = "rs")]
-    pub suffix: String,
+    /// Suffix of the generated file, example: -s rs, defaults to the suffix of --lang
+    #[structopt(short = "s")]
+    pub suffix: Option<String>,
+    /// Exit with an error when any field type is not mapped, example: --strict
+    #[structopt(long = "strict")]
+    pub strict: bool,
+    /// Language of the generated code, rust, typescript, go, python, java or kotlin, example: --lang go
+    #[structopt(long = "lang", default_value = "rust")]
+    pub lang: Lang,
+    /// Library used by the generated Rust code, sqlx, diesel or seaorm, example: --target diesel
+    #[structopt(long = "target", default_value = "sqlx")]
+    pub target: Target,
 }
 
+#[cfg(feature = "clickhouse")]
 #[derive(Debug, StructOpt)]
 pub struct Clickhouse {
     /// Input database config file to read, example: -f ./reverse.yml
@@ -94,17 +133,24 @@ pub struct Clickhouse {
     /// Input template path example: -p 'templates/*'
     #[structopt(short = "p", default_value = "templates/*")]
     pub template_path: String,
-    /// Input template name, example: -n clickhouse.tera
-    #[structopt(short = "n", default_value = "clickhouse.tera")]
-    pub template_name: String,
+    /// Input template name, example: -n clickhouse.tera, defaults to clickhouse.tera, or <lang>.tera with --lang
+    #[structopt(short = "n")]
+    pub template_name: Option<String>,
     /// Custom field type, example: -c ./default.json
     #[structopt(short = "c", default_value = "")]
     pub custom_field_type: String,
-    /// Suffix of the generated file, example: -s rs
-    #[structopt(short = "s", default_value = "rs")]
-    pub suffix: String,
+    /// Suffix of the generated file, example: -s rs, defaults to the suffix of --lang
+    #[structopt(short = "s")]
+    pub suffix: Option<String>,
+    /// Exit with an error when any field type is not mapped, example: --strict
+    #[structopt(long = "strict")]
+    pub strict: bool,
+    /// Language of the generated code, rust, typescript, go, python, java or kotlin, example: --lang go
+    #[structopt(long = "lang", default_value = "rust")]
+    pub lang: Lang,
 }
 
+#[cfg(feature = "tdengine")]
 #[derive(Debug, StructOpt)]
 pub struct Tdengine {
     /// Input database config file to read, example: -f ./reverse.yml
@@ -113,13 +159,19 @@ pub struct Tdengine {
     /// Input template path example: -p 'templates/*'
     #[structopt(short = "p", default_value = "templates/*")]
     pub template_path: String,
-    /// Input template name, example: -n tdengine.tera
-    #[structopt(short = "n", default_value = "tdengine.tera")]
-    pub template_name: String,
+    /// Input template name, example: -n tdengine.tera, defaults to tdengine.tera, or <lang>.tera with --lang
+    #[structopt(short = "n")]
+    pub template_name: Option<String>,
     /// Custom field type, example: -c ./default.json
     #[structopt(short = "c", default_value = "")]
     pub custom_field_type: String,
-    /// Suffix of the generated file, example: -s rs
-    #[structopt(short = "s", default_value = "rs")]
-    pub suffix: String,
+    /// Suffix of the generated file, example: -s rs, defaults to the suffix of --lang
+    #[structopt(short = "s")]
+    pub suffix: Option<String>,
+    /// Exit with an error when any field type is not mapped, example: --strict
+    #[structopt(long = "strict")]
+    pub strict: bool,
+    /// Language of the generated code, rust, typescript, go, python, java or kotlin, example: --lang go
+    #[structopt(long = "lang", default_value = "rust")]
+    pub lang: Lang,
 }
@@ -1,6 +1,9 @@
+use crate::template::template_type::Target;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error(transparent)]
     Regex(#[from] regex::Error),
@@ -14,14 +17,30 @@ pub enum Error {
     Clap(#[from] clap::Error),
     #[error(transparent)]
     Tera(#[from] tera::Error),
+    #[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
     #[error(transparent)]
     Sqlx(#[from] sqlx::Error),
     #[error(transparent)]
     FromUtf8(#[from] std::string::FromUtf8Error),
     #[error(transparent)]
     ParseInt(#[from] std::num::ParseIntError),
+    #[cfg(feature = "clickhouse")]
     #[error(transparent)]
     Clickhouse(#[from] clickhouse::error::Error),
+    #[cfg(feature = "clickhouse")]
+    #[error("failed to connect to ClickHouse at {url}: {source}")]
+    ClickhouseConnect {
+        url: String,
+        source: clickhouse::error::Error,
+    },
+    #[cfg(feature = "tdengine")]
     #[error(transparent)]
     Taos(#[from] taos::Error),
+    #[cfg(feature = "tdengine")]
+    #[error("TDengine DSN scheme {0:?} needs the `{1}` cargo feature")]
+    TaosFeature(String, &'static str),
+    #[error("strict mode, unmapped field types:\n  {}", .0.join("\n  "))]
+    UnmappedFieldTypes(Vec<String>),
+    #[error("--target {0:?} is only supported with --lang rust, got --lang {1}")]
+    UnsupportedTarget(Target, String),
 }
@@ -0,0 +1,43 @@
+use crate::keywords::Keywords;
+use fn_macro::hashmap;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Go 关键字, 预声明标识符(int, string, nil 等)可以被遮蔽, 不需要转义
+pub static GO_KEYS: LazyLock<HashMap<&str, ()>> = LazyLock::new(|| {
+    hashmap!(
+        "break" => (),
+        "case" => (),
+        "chan" => (),
+        "const" => (),
+        "continue" => (),
+        "default" => (),
+        "defer" => (),
+        "else" => (),
+        "fallthrough" => (),
+        "for" => (),
+        "func" => (),
+        "go" => (),
+        "goto" => (),
+        "if" => (),
+        "import" => (),
+        "interface" => (),
+        "map" => (),
+        "package" => (),
+        "range" => (),
+        "return" => (),
+        "select" => (),
+        "struct" => (),
+        "switch" => (),
+        "type" => (),
+        "var" => (),
+    )
+});
+
+pub struct Go;
+
+impl Keywords for Go {
+    fn is_keyword(&self, name: &str) -> bool {
+        GO_KEYS.contains_key(name)
+    }
+}
@@ -0,0 +1,76 @@
+use crate::keywords::Keywords;
+use fn_macro::hashmap;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Java 关键字和保留字面量, 以及不能作为类型名的 var/yield/record/sealed/permits
+pub static JAVA_KEYS: LazyLock<HashMap<&str, ()>> = LazyLock::new(|| {
+    hashmap!(
+        "abstract" => (),
+        "assert" => (),
+        "boolean" => (),
+        "break" => (),
+        "byte" => (),
+        "case" => (),
+        "catch" => (),
+        "char" => (),
+        "class" => (),
+        "const" => (),
+        "continue" => (),
+        "default" => (),
+        "do" => (),
+        "double" => (),
+        "else" => (),
+        "enum" => (),
+        "extends" => (),
+        "final" => (),
+        "finally" => (),
+        "float" => (),
+        "for" => (),
+        "goto" => (),
+        "if" => (),
+        "implements" => (),
+        "import" => (),
+        "instanceof" => (),
+        "int" => (),
+        "interface" => (),
+        "long" => (),
+        "native" => (),
+        "new" => (),
+        "package" => (),
+        "private" => (),
+        "protected" => (),
+        "public" => (),
+        "return" => (),
+        "short" => (),
+        "static" => (),
+        "strictfp" => (),
+        "super" => (),
+        "switch" => (),
+        "synchronized" => (),
+        "this" => (),
+        "throw" => (),
+        "throws" => (),
+        "transient" => (),
+        "try" => (),
+        "void" => (),
+        "volatile" => (),
+        "while" => (),
+        "true" => (),
+        "false" => (),
+        "null" => (),
+        "var" => (),
+        "yield" => (),
+        "record" => (),
+        "sealed" => (),
+        "permits" => (),
+    )
+});
+
+pub struct Java;
+
+impl Keywords for Java {
+    fn is_keyword(&self, name: &str) -> bool {
+        JAVA_KEYS.contains_key(name)
+    }
+}
@@ -0,0 +1,50 @@
+use crate::keywords::Keywords;
+use fn_macro::hashmap;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Kotlin 硬关键字, 软关键字和修饰符关键字可以作为标识符
+pub static KOTLIN_KEYS: LazyLock<HashMap<&str, ()>> = LazyLock::new(|| {
+    hashmap!(
+        "as" => (),
+        "break" => (),
+        "class" => (),
+        "continue" => (),
+        "do" => (),
+        "else" => (),
+        "false" => (),
+        "for" => (),
+        "fun" => (),
+        "if" => (),
+        "in" => (),
+        "interface" => (),
+        "is" => (),
+        "null" => (),
+        "object" => (),
+        "package" => (),
+        "return" => (),
+        "super" => (),
+        "this" => (),
+        "throw" => (),
+        "true" => (),
+        "try" => (),
+        "typealias" => (),
+        "typeof" => (),
+        "val" => (),
+        "var" => (),
+        "when" => (),
+        "while" => (),
+    )
+});
+
+pub struct Kotlin;
+
+impl Keywords for Kotlin {
+    fn is_keyword(&self, name: &str) -> bool {
+        KOTLIN_KEYS.contains_key(name)
+    }
+
+    fn raw_identifier(&self, name: &str) -> Option<String> {
+        Some(format!("`{}`", name))
+    }
+}
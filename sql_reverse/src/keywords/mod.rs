@@ -1,29 +1,133 @@
-use std::sync::LazyLock;
-use tokio::sync::OnceCell;
+use serde::{Deserialize, Serialize};
 
+mod go;
+mod java;
+mod kotlin;
+mod python;
 mod rust;
+mod typescript;
 
 pub struct Default;
 
 impl Keywords for Default {}
 
+/// 保留字转义方式
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, std::default::Default)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Escape {
+    /// 原始标识符, 例如 Rust 的 r#type, Kotlin 的 `in`, 语言不支持时追加 _
+    #[default]
+    Raw,
+    /// 追加 _, 例如 type_
+    Suffix,
+    /// 追加 _, 模板按 Field.rename 生成重命名属性, 例如 #[serde(rename = "type")]
+    Rename,
+}
+
 pub trait Keywords {
-    fn check_field_name(&self, field_name: &str) -> String {
-        field_name.to_string()
+    /// 是否为保留字
+    fn is_keyword(&self, _name: &str) -> bool {
+        false
+    }
+
+    /// 原始标识符写法, 语言不支持或该保留字不能作为原始标识符时返回 None
+    fn raw_identifier(&self, _name: &str) -> Option<String> {
+        None
+    }
+
+    /// 按 escape 转义保留字
+    fn escape(&self, name: &str, escape: Escape) -> String {
+        if !self.is_keyword(name) {
+            return name.to_string();
+        }
+        match escape {
+            Escape::Raw => self
+                .raw_identifier(name)
+                .unwrap_or_else(|| format!("{}_", name)),
+            Escape::Suffix | Escape::Rename => format!("{}_", name),
+        }
+    }
+}
+
+/// 文件后缀对应语言的保留字, 未知后缀没有保留字
+fn keywords(suffix: &str) -> &'static (dyn Keywords + Send + Sync) {
+    match suffix {
+        "rs" => &rust::Rust,
+        "go" => &go::Go,
+        "ts" => &typescript::TypeScript,
+        "py" => &python::Python,
+        "java" => &java::Java,
+        "kt" => &kotlin::Kotlin,
+        _ => &Default,
+    }
+}
+
+/// 生成代码的命名: 按语言的保留字和转义方式转义结构体名、字段名和模块名
+#[derive(Clone, Copy)]
+pub struct Naming {
+    keywords: &'static (dyn Keywords + Send + Sync),
+    escape: Escape,
+}
+
+impl Naming {
+    /// suffix 为生成文件的后缀
+    pub fn new(suffix: &str, escape: Escape) -> Self {
+        Self {
+            keywords: keywords(suffix),
+            escape,
+        }
+    }
+
+    pub fn escape(&self) -> Escape {
+        self.escape
+    }
+
+    pub fn field_name(&self, field_name: &str) -> String {
+        self.keywords.escape(field_name, self.escape)
+    }
+
+    pub fn struct_name(&self, struct_name: &str) -> String {
+        self.keywords.escape(struct_name, self.escape)
+    }
+
+    /// 模块名, 原始标识符写法只用于 mod 声明, 文件名见 module_file_name
+    pub fn module_name(&self, module_name: &str) -> String {
+        self.keywords.escape(module_name, self.escape)
     }
 }
 
-pub static LANGUAGE: LazyLock<Box<dyn Keywords + Send + Sync>> =
-    LazyLock::new(|| match LANGUAGE_ONCE.get().unwrap().as_str() {
-        "rs" => Box::new(rust::Rust),
-        _ => Box::new(Default),
-    });
+/// 模块标识符对应的文件名, 去掉原始标识符前缀
+pub fn module_file_name(module_name: &str) -> &str {
+    module_name.trim_start_matches("r#").trim_matches('`')
+}
 
-pub static LANGUAGE_ONCE: OnceCell<String> = OnceCell::const_new();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-pub async fn get_or_init(language: &str) -> String {
-    LANGUAGE_ONCE
-        .get_or_init(|| async move { language.to_string() })
-        .await
-        .clone()
+    #[test]
+    fn test_escape() {
+        let raw = Escape::Raw;
+        assert_eq!(rust::Rust.escape("type", raw), "r#type");
+        assert_eq!(rust::Rust.escape("yield", raw), "r#yield");
+        assert_eq!(rust::Rust.escape("type", Escape::Suffix), "type_");
+        // self/Self/super/crate 不能作为原始标识符
+        assert_eq!(rust::Rust.escape("self", raw), "self_");
+        assert_eq!(Naming::new("rs", raw).struct_name("Self"), "Self_");
+        assert_eq!(rust::Rust.escape("name", raw), "name");
+        assert_eq!(kotlin::Kotlin.escape("in", raw), "`in`");
+        assert_eq!(Naming::new("py", raw).struct_name("None"), "None_");
+        assert_eq!(go::Go.escape("range", raw), "range_");
+        assert_eq!(java::Java.escape("default", raw), "default_");
+        assert_eq!(
+            typescript::TypeScript.escape("interface", raw),
+            "interface_"
+        );
+        // 生成文件的后缀决定保留字, 与其他调用互不影响
+        assert_eq!(Naming::new("kt", raw).field_name("type"), "type");
+        assert_eq!(Naming::new("rs", raw).field_name("type"), "r#type");
+        assert_eq!(module_file_name("r#type"), "type");
+        assert_eq!(module_file_name("`in`"), "in");
+    }
 }
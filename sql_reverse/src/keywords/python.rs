@@ -0,0 +1,53 @@
+use crate::keywords::Keywords;
+use fn_macro::hashmap;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Python 关键字, 不含 match/case/type 等软关键字
+pub static PYTHON_KEYS: LazyLock<HashMap<&str, ()>> = LazyLock::new(|| {
+    hashmap!(
+        "False" => (),
+        "None" => (),
+        "True" => (),
+        "and" => (),
+        "as" => (),
+        "assert" => (),
+        "async" => (),
+        "await" => (),
+        "break" => (),
+        "class" => (),
+        "continue" => (),
+        "def" => (),
+        "del" => (),
+        "elif" => (),
+        "else" => (),
+        "except" => (),
+        "finally" => (),
+        "for" => (),
+        "from" => (),
+        "global" => (),
+        "if" => (),
+        "import" => (),
+        "in" => (),
+        "is" => (),
+        "lambda" => (),
+        "nonlocal" => (),
+        "not" => (),
+        "or" => (),
+        "pass" => (),
+        "raise" => (),
+        "return" => (),
+        "try" => (),
+        "while" => (),
+        "with" => (),
+        "yield" => (),
+    )
+});
+
+pub struct Python;
+
+impl Keywords for Python {
+    fn is_keyword(&self, name: &str) -> bool {
+        PYTHON_KEYS.contains_key(name)
+    }
+}
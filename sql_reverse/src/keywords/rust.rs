@@ -3,53 +3,80 @@ use fn_macro::hashmap;
 use std::collections::HashMap;
 use std::sync::LazyLock;
 
+/// 所有版本的严格关键字和保留关键字, 以及 2018 之后的 async/await/dyn/try, 2024 的 gen
 pub static RUST_KEYS: LazyLock<HashMap<&str, ()>> = LazyLock::new(|| {
     hashmap!(
-        "if" => (),
-        "else" => (),
-        "loop" => (),
-        "while" => (),
-        "for" => (),
+        "as" => (),
         "break" => (),
+        "const" => (),
         "continue" => (),
-        "return" => (),
-        "match" => (),
+        "crate" => (),
+        "else" => (),
+        "enum" => (),
+        "extern" => (),
+        "false" => (),
         "fn" => (),
+        "for" => (),
+        "if" => (),
+        "impl" => (),
+        "in" => (),
         "let" => (),
+        "loop" => (),
+        "match" => (),
+        "mod" => (),
+        "move" => (),
         "mut" => (),
-        "const" => (),
+        "pub" => (),
+        "ref" => (),
+        "return" => (),
+        "self" => (),
+        "Self" => (),
         "static" => (),
         "struct" => (),
-        "enum" => (),
-        "union" =>(),
-        "impl" => (),
+        "super" => (),
         "trait" => (),
+        "true" => (),
         "type" => (),
+        "unsafe" => (),
         "use" => (),
-        "mod" => (),
-        "pub" => (),
-        "crate" => (),
-        "self" => (),
-        "Self" => (),
-        "super" => (),
-        "dyn" => (),
-        "move" => (),
-        "ref" => (),
+        "where" => (),
+        "while" => (),
         "async" => (),
         "await" => (),
-        "unsafe" => (),
-        "as" => (),
+        "dyn" => (),
+        "abstract" => (),
+        "become" => (),
+        "box" => (),
+        "do" => (),
+        "final" => (),
+        "macro" => (),
+        "override" => (),
+        "priv" => (),
+        "typeof" => (),
+        "unsized" => (),
+        "virtual" => (),
+        "yield" => (),
+        "try" => (),
+        "gen" => (),
+        "union" => (),
     )
 });
 
+/// 不能写成 r#name 的关键字
+const NON_RAW_KEYS: [&str; 4] = ["crate", "self", "Self", "super"];
+
 pub struct Rust;
 
 impl Keywords for Rust {
-    fn check_field_name(&self, field_name: &str) -> String {
-        if RUST_KEYS.contains_key(field_name) {
-            format!("r#{}", field_name)
+    fn is_keyword(&self, name: &str) -> bool {
+        RUST_KEYS.contains_key(name)
+    }
+
+    fn raw_identifier(&self, name: &str) -> Option<String> {
+        if NON_RAW_KEYS.contains(&name) {
+            None
         } else {
-            field_name.to_string()
+            Some(format!("r#{}", name))
         }
     }
 }
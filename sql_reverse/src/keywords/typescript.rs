@@ -0,0 +1,74 @@
+use crate::keywords::Keywords;
+use fn_macro::hashmap;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// TypeScript 保留字, 严格模式保留字, 以及不能作为类型名的内置类型
+pub static TYPESCRIPT_KEYS: LazyLock<HashMap<&str, ()>> = LazyLock::new(|| {
+    hashmap!(
+        "break" => (),
+        "case" => (),
+        "catch" => (),
+        "class" => (),
+        "const" => (),
+        "continue" => (),
+        "debugger" => (),
+        "default" => (),
+        "delete" => (),
+        "do" => (),
+        "else" => (),
+        "enum" => (),
+        "export" => (),
+        "extends" => (),
+        "false" => (),
+        "finally" => (),
+        "for" => (),
+        "function" => (),
+        "if" => (),
+        "import" => (),
+        "in" => (),
+        "instanceof" => (),
+        "new" => (),
+        "null" => (),
+        "return" => (),
+        "super" => (),
+        "switch" => (),
+        "this" => (),
+        "throw" => (),
+        "true" => (),
+        "try" => (),
+        "typeof" => (),
+        "var" => (),
+        "void" => (),
+        "while" => (),
+        "with" => (),
+        "as" => (),
+        "implements" => (),
+        "interface" => (),
+        "let" => (),
+        "package" => (),
+        "private" => (),
+        "protected" => (),
+        "public" => (),
+        "static" => (),
+        "yield" => (),
+        "any" => (),
+        "boolean" => (),
+        "never" => (),
+        "number" => (),
+        "object" => (),
+        "string" => (),
+        "symbol" => (),
+        "bigint" => (),
+        "undefined" => (),
+        "unknown" => (),
+    )
+});
+
+pub struct TypeScript;
+
+impl Keywords for TypeScript {
+    fn is_keyword(&self, name: &str) -> bool {
+        TYPESCRIPT_KEYS.contains_key(name)
+    }
+}
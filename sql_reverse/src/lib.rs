@@ -0,0 +1,104 @@
+//! 基于数据库表结构和自定义模版生成多种编程语言代码, 支持 MySQL、Postgres、Sqlite、ClickHouse、TDengine。
+//!
+//! 命令行工具 `sql_reverse` 只是这个库的一层封装, 也可以在 build.rs 或其他工具中直接使用:
+//!
+//! ```no_run
+//! use sql_reverse::{CustomConfig, Lang, Renderer, TemplateType, connect};
+//!
+//! # async fn run() -> sql_reverse::Result<()> {
+//! let config = CustomConfig::load("./reverse.yml").await?;
+//! let template_type = TemplateType::default();
+//! let renderer = Renderer::new(template_type)
+//!     .lang(Lang::Rust)
+//!     .template_path("templates/*")
+//!     .config(&config);
+//! let output_dir = config.output_dir.clone();
+//! let db = connect(template_type, config).await?;
+//! let tables = renderer.tables(db.as_ref()).await?;
+//! renderer.render(&tables, &output_dir).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! 也可以手动构建表结构, 结构体名、模块名和字段名在 render 时按语言和转义方式生成:
+//!
+//! ```no_run
+//! use sql_reverse::{Field, Renderer, Table, TemplateType};
+//!
+//! # async fn run() -> sql_reverse::Result<()> {
+//! let mut name = Field::new("name", "varchar(64)", "String");
+//! name.is_null = 1;
+//! let mut users = Table::new("users", vec![Field::new("id", "bigint", "i64"), name]);
+//! users.primary_key = vec!["id".to_string()];
+//! Renderer::new(TemplateType::default())
+//!     .render(&[users], "src/models")
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! 每个数据库对应一个 cargo feature: mysql, postgres, sqlite, clickhouse, tdengine (WebSocket 协议, 原生协议另外启用 taos-native),
+//! [`TemplateType`] 只包含已启用的数据库。
+//!
+//! 语言、目标库、保留字转义、类型映射等配置都保存在 [`Renderer`] 中, 同一进程中可以同时使用多个 [`Renderer`]。
+
+#[cfg(not(any(
+    feature = "mysql",
+    feature = "postgres",
+    feature = "sqlite",
+    feature = "clickhouse",
+    feature = "tdengine"
+)))]
+compile_error!("enable at least one of the mysql, postgres, sqlite, clickhouse, tdengine features");
+
+/// 没有启用任何数据库时 TemplateType 为空, 不编译库的其他部分, 只报上面的 compile_error
+macro_rules! with_database {
+    ($($item:item)*) => {
+        $(
+            #[cfg(any(
+                feature = "mysql",
+                feature = "postgres",
+                feature = "sqlite",
+                feature = "clickhouse",
+                feature = "tdengine"
+            ))]
+            $item
+        )*
+    };
+}
+
+with_database! {
+    mod error;
+    mod keywords;
+    mod reverse_impl;
+    mod table;
+    mod template;
+
+    pub use crate::error::{Error, Result};
+    pub use crate::keywords::Escape;
+    #[cfg(feature = "clickhouse")]
+    pub use crate::reverse_impl::clickhouse_impl::ClickhouseImpl;
+    pub use crate::reverse_impl::common::{
+        ClickhouseCompression, ClickhouseConfig, ConnectionConfig, ConnectionMode, CustomConfig,
+        SoftDeleteConfig,
+    };
+    pub use crate::reverse_impl::export::export;
+    pub use crate::reverse_impl::gen_struct::{BoxFuture, DynGenStruct, GenStruct, connect};
+    #[cfg(feature = "mysql")]
+    pub use crate::reverse_impl::mysql_impl::MysqlImpl;
+    #[cfg(feature = "postgres")]
+    pub use crate::reverse_impl::postgres_impl::PostgresImpl;
+    #[cfg(feature = "sqlite")]
+    pub use crate::reverse_impl::sqlite_impl::SqliteImpl;
+    #[cfg(feature = "tdengine")]
+    pub use crate::reverse_impl::tdengine_impl::TdengineImpl;
+    pub use crate::reverse_impl::type_map::{FieldTypes, TypeMap};
+    pub use crate::table::{
+        CustomType, CustomTypeMember, Engine, Field, ForeignKey, SoftDelete, Table, Table2Comment,
+        TableKind,
+    };
+    pub use crate::template::kit::Kit;
+    pub use crate::template::lang::Lang;
+    pub use crate::template::render::Renderer;
+    pub use crate::template::template_type::{Target, TemplateType};
+}
@@ -1,111 +1,77 @@
 use app_arguments::{ApplicationArguments, Command};
-mod app_arguments;
-mod error;
-mod keywords;
-mod reverse_impl;
-mod table;
-mod template;
-
-use crate::error::Result;
-use crate::keywords::get_or_init;
-use crate::reverse_impl::export::export;
-use crate::reverse_impl::gen_struct::GenStruct;
-use crate::reverse_impl::clickhouse_impl::ClickhouseImpl;
-use crate::reverse_impl::mysql_impl::MysqlImpl;
-use crate::reverse_impl::postgres_impl::PostgresImpl;
-use crate::reverse_impl::sqlite_impl::SqliteImpl;
-use crate::reverse_impl::tdengine_impl::TdengineImpl;
-use crate::table::Table;
-use crate::template::kit::Kit;
-use crate::template::render::Render;
-use crate::template::template_type::{TemplateType, update_template_type};
+use sql_reverse::{CustomConfig, Lang, Renderer, Result, TemplateType, TypeMap, connect, export};
 use structopt::StructOpt;
+mod app_arguments;
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() {
+    if let Err(e) = run().await {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run() -> Result<()> {
     let args = ApplicationArguments::from_args();
     let key = args.command;
     match key {
+        #[cfg(feature = "mysql")]
         Command::Mysql(opt) => {
-            update_template_type(TemplateType::Mysql);
-            get_or_init(&opt.suffix).await;
-            let config = MysqlImpl::load(&opt.file).await?;
-            let mysql = MysqlImpl::init(config).await?;
-            let tables = mysql.run(&opt.custom_field_type).await?;
-            Table::check_download_tera(&opt.template_path, &opt.template_name).await?;
-            Table::render_rust(
-                &opt.template_path,
-                &opt.template_name,
-                &opt.suffix,
-                &mysql.config.output_dir,
-                &tables,
+            let renderer = renderer(
+                TemplateType::Mysql,
+                opt.lang,
+                opt.template_path,
+                opt.template_name,
+                opt.suffix,
             )
-            .await?;
+            .target(opt.target);
+            reverse(renderer, &opt.file, &opt.custom_field_type, opt.strict).await?;
         }
-
+        #[cfg(feature = "postgres")]
         Command::Postgres(opt) => {
-            update_template_type(TemplateType::Postgres);
-            get_or_init(&opt.suffix).await;
-            let config = PostgresImpl::load(&opt.file).await?;
-            let postgres = PostgresImpl::init(config).await?;
-            let tables = postgres.run(&opt.custom_field_type).await?;
-            Table::check_download_tera(&opt.template_path, &opt.template_name).await?;
-            Table::render_rust(
-                &opt.template_path,
-                &opt.template_name,
-                &opt.suffix,
-                &postgres.config.output_dir,
-                &tables,
+            let renderer = renderer(
+                TemplateType::Postgres,
+                opt.lang,
+                opt.template_path,
+                opt.template_name,
+                opt.suffix,
             )
-            .await?;
+            .target(opt.target);
+            reverse(renderer, &opt.file, &opt.custom_field_type, opt.strict).await?;
         }
+        #[cfg(feature = "sqlite")]
         Command::Sqlite(opt) => {
-            update_template_type(TemplateType::Sqlite);
-            get_or_init(&opt.suffix).await;
-            let config = SqliteImpl::load(&opt.file).await?;
-            let sqlite = SqliteImpl::init(config).await?;
-            let tables = sqlite.run(&opt.custom_field_type).await?;
-            Table::check_download_tera(&opt.template_path, &opt.template_name).await?;
-            Table::render_rust(
-                &opt.template_path,
-                &opt.template_name,
-                &opt.suffix,
-                &sqlite.config.output_dir,
-                &tables,
+            let renderer = renderer(
+                TemplateType::Sqlite,
+                opt.lang,
+                opt.template_path,
+                opt.template_name,
+                opt.suffix,
             )
-            .await?;
+            .target(opt.target);
+            reverse(renderer, &opt.file, &opt.custom_field_type, opt.strict).await?;
         }
+        #[cfg(feature = "clickhouse")]
         Command::Clickhouse(opt) => {
-            update_template_type(TemplateType::Clickhouse);
-            get_or_init(&opt.suffix).await;
-            let config = ClickhouseImpl::load(&opt.file).await?;
-            let clickhouse = ClickhouseImpl::init(config).await?;
-            let tables = clickhouse.run(&opt.custom_field_type).await?;
-            Table::check_download_tera(&opt.template_path, &opt.template_name).await?;
-            Table::render_rust(
-                &opt.template_path,
-                &opt.template_name,
-                &opt.suffix,
-                &clickhouse.config.output_dir,
-                &tables,
-            )
-            .await?;
+            let renderer = renderer(
+                TemplateType::Clickhouse,
+                opt.lang,
+                opt.template_path,
+                opt.template_name,
+                opt.suffix,
+            );
+            reverse(renderer, &opt.file, &opt.custom_field_type, opt.strict).await?;
         }
+        #[cfg(feature = "tdengine")]
         Command::Tdengine(opt) => {
-            update_template_type(TemplateType::Tdengine);
-            get_or_init(&opt.suffix).await;
-            let config = TdengineImpl::load(&opt.file).await?;
-            let tdengine = TdengineImpl::init(config).await?;
-            let tables = tdengine.run(&opt.custom_field_type).await?;
-            Table::check_download_tera(&opt.template_path, &opt.template_name).await?;
-            Table::render_rust(
-                &opt.template_path,
-                &opt.template_name,
-                &opt.suffix,
-                &tdengine.config.output_dir,
-                &tables,
-            )
-            .await?;
+            let renderer = renderer(
+                TemplateType::Tdengine,
+                opt.lang,
+                opt.template_path,
+                opt.template_name,
+                opt.suffix,
+            );
+            reverse(renderer, &opt.file, &opt.custom_field_type, opt.strict).await?;
         }
         Command::Export => {
             export().await?;
@@ -113,3 +79,41 @@ async fn main() -> Result<()> {
     }
     Ok(())
 }
+
+/// 命令行参数对应的渲染配置
+fn renderer(
+    template_type: TemplateType,
+    lang: Lang,
+    template_path: String,
+    template_name: Option<String>,
+    suffix: Option<String>,
+) -> Renderer {
+    let mut renderer = Renderer::new(template_type)
+        .lang(lang)
+        .template_path(template_path);
+    if let Some(template_name) = template_name {
+        renderer = renderer.template_name(template_name);
+    }
+    if let Some(suffix) = suffix {
+        renderer = renderer.suffix(suffix);
+    }
+    renderer
+}
+
+/// 读取配置文件, 连接数据库并生成代码, custom_field_type 为自定义字段类型的 json 文件, 为空时使用默认类型映射
+async fn reverse(
+    renderer: Renderer,
+    file: &str,
+    custom_field_type: &str,
+    strict: bool,
+) -> Result<()> {
+    let config = CustomConfig::load(file).await?;
+    let mut renderer = renderer.config(&config).strict(strict);
+    if !custom_field_type.is_empty() {
+        renderer = renderer.type_map(TypeMap::load(custom_field_type).await?);
+    }
+    let output_dir = config.output_dir.to_owned();
+    let db = connect(renderer.template_type(), config).await?;
+    let tables = renderer.tables(db.as_ref()).await?;
+    renderer.render(&tables, &output_dir).await
+}
@@ -1,17 +1,18 @@
-use crate::error::Result;
-use crate::keywords::LANGUAGE;
-use crate::reverse_impl::common::CustomConfig;
+use crate::error::{Error, Result};
+use crate::reverse_impl::clickhouse_type::ClickhouseType;
+use crate::reverse_impl::common::{ClickhouseCompression, CustomConfig};
 use crate::reverse_impl::gen_struct::GenStruct;
-use crate::table::clickhouse::{ClickhouseField, ClickhouseTable};
-use crate::table::{Field, Table, Table2Comment};
-use crate::template::kit::Kit;
+use crate::reverse_impl::type_map::FieldTypes;
+use crate::table::clickhouse::{ClickhouseEngine, ClickhouseField, ClickhouseTable};
+use crate::table::{CustomType, CustomTypeMember, Engine, Field, Table, Table2Comment};
+use crate::template::kit::{Kit, identifier, pascal_identifier};
 use fn_macro::btreemap;
 use inflector::Inflector;
-use std::collections::BTreeMap;
-use std::sync::{LazyLock, RwLock};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::LazyLock;
 
-pub static FIELD_TYPE: LazyLock<RwLock<BTreeMap<String, String>>> = LazyLock::new(|| {
-    let map = btreemap!(
+pub static FIELD_TYPE: LazyLock<BTreeMap<String, String>> = LazyLock::new(|| {
+    btreemap!(
         // Integer types
         "^Int8$".to_string() => "i8".to_string(),
         "^Int16$".to_string() => "i16".to_string(),
@@ -56,20 +57,22 @@ pub static FIELD_TYPE: LazyLock<RwLock<BTreeMap<String, String>>> = LazyLock::ne
         // JSON types
         "^JSON$".to_string() => "serde_json::Value".to_string(),
         "^Object\\('json'\\)$".to_string() => "serde_json::Value".to_string()
-    );
-    RwLock::new(map)
+    )
 });
 
 #[derive(Clone)]
 pub struct ClickhouseImpl {
     pub config: CustomConfig,
     pub client: clickhouse::Client,
+    /// 实际使用的数据库, 依次取 schemaname、db_url 中的路径, 都没有时为 default
+    pub database: String,
 }
 
 impl std::fmt::Debug for ClickhouseImpl {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ClickhouseImpl")
             .field("config", &self.config)
+            .field("database", &self.database)
             .finish()
     }
 }
@@ -78,8 +81,48 @@ impl Kit for ClickhouseImpl {}
 
 impl ClickhouseImpl {
     pub async fn init(config: CustomConfig) -> Result<Self> {
-        let client = clickhouse::Client::default().with_url(&config.db_url);
-        Ok(Self { config, client })
+        let ch_config = config.clickhouse.clone().unwrap_or_default();
+        let url = ClickhouseUrl::parse(&config.db_url);
+        let database = config
+            .schemaname
+            .clone()
+            .or(url.database)
+            .unwrap_or_else(|| "default".to_string());
+        let mut client = clickhouse::Client::default()
+            .with_url(&url.url)
+            .with_database(&database);
+        if let Some(user) = ch_config.user.or(url.user) {
+            client = client.with_user(user);
+        }
+        if let Some(password) = ch_config.password.or(url.password) {
+            client = client.with_password(password);
+        }
+        if let Some(compression) = ch_config.compression {
+            client = client.with_compression(match compression {
+                ClickhouseCompression::None => clickhouse::Compression::None,
+                ClickhouseCompression::Lz4 => clickhouse::Compression::Lz4,
+            });
+        }
+        for (name, value) in url.options {
+            client = client.with_option(name, value);
+        }
+        for (name, value) in ch_config.headers {
+            client = client.with_header(name, value);
+        }
+        // 启动时检查地址、账号和数据库, 避免在读取表结构时才报出难以定位的错误
+        client
+            .query("SELECT 1")
+            .fetch_one::<u8>()
+            .await
+            .map_err(|source| Error::ClickhouseConnect {
+                url: url.url.clone(),
+                source,
+            })?;
+        Ok(Self {
+            config,
+            client,
+            database,
+        })
     }
 
     /// Strip LowCardinality(...) wrapper
@@ -108,14 +151,22 @@ impl ClickhouseImpl {
         Self::strip_nullable(&stripped)
     }
 
-    fn convert_field(ch_field: &ClickhouseField) -> Field {
+    /// type_name: 字段类型需要生成枚举或结构体时使用的类型名
+    fn convert_field(
+        table_name: &str,
+        ch_field: &ClickhouseField,
+        type_name: &str,
+        collector: &mut TypeCollector,
+    ) -> Field {
         let (inner_type, is_nullable) = Self::normalize_type(&ch_field.field_type);
-        let field_type = Self::get_field_type(
-            &inner_type,
-            &ch_field.name,
-            &FIELD_TYPE.read().unwrap(),
-        )
-        .unwrap_or_default();
+        let column = format!("{}.{}", table_name, ch_field.name);
+        let field_type = match ClickhouseType::parse(&inner_type) {
+            Some(ty) => collector.rust_type(&ty, type_name, &column, true),
+            None => collector
+                .field_types
+                .field_type(&inner_type, &column)
+                .unwrap_or_default(),
+        };
         let field_name_camel_case = ch_field.name.clone().to_camel_case();
         let first_char_uppercase = Self::first_char_to_uppercase(&field_name_camel_case);
         let default = if ch_field.default_expression.is_empty() {
@@ -123,15 +174,50 @@ impl ClickhouseImpl {
         } else {
             Some(ch_field.default_expression.clone())
         };
+        let default_kind = if ch_field.default_kind.is_empty() {
+            None
+        } else {
+            Some(ch_field.default_kind.clone())
+        };
+        let generated = matches!(ch_field.default_kind.as_str(), "MATERIALIZED" | "ALIAS");
         Field {
-            field_name: LANGUAGE.check_field_name(&ch_field.name),
+            column_name: ch_field.name.to_owned(),
+            field_name: ch_field.name.to_owned(),
             FieldName: first_char_uppercase,
-            fieldName: LANGUAGE.check_field_name(&field_name_camel_case),
+            fieldName: field_name_camel_case,
             database_field_type: ch_field.field_type.clone(),
             field_type,
             comment: ch_field.comment.clone(),
             is_null: if is_nullable { 1 } else { 0 },
             default,
+            rename: None,
+            default_kind,
+            generated,
+            is_tag: false,
+        }
+    }
+
+    fn convert_engine(ch_engine: ClickhouseEngine) -> Engine {
+        let ttl = Self::table_ttl(&ch_engine.engine_full);
+        Engine {
+            name: ch_engine.engine,
+            engine_full: ch_engine.engine_full,
+            partition_key: ch_engine.partition_key,
+            sorting_key: ch_engine.sorting_key,
+            primary_key: ch_engine.primary_key,
+            sampling_key: ch_engine.sampling_key,
+            ttl,
+        }
+    }
+
+    /// system.tables 没有单独的 TTL 列, 从 engine_full 中截取 `TTL ...` 到 `SETTINGS` 之前的部分
+    fn table_ttl(engine_full: &str) -> String {
+        let Some((_, ttl)) = engine_full.split_once(" TTL ") else {
+            return String::new();
+        };
+        match ttl.split_once(" SETTINGS ") {
+            Some((ttl, _)) => ttl.trim().to_string(),
+            None => ttl.trim().to_string(),
         }
     }
 
@@ -161,16 +247,267 @@ impl ClickhouseImpl {
     }
 }
 
+/// 收集字段类型中生成的枚举和结构体, 成员名在 GenStruct::run 中按语言转义
+struct TypeCollector<'a> {
+    types: Vec<CustomType>,
+    /// 已使用的类型名, 包括表结构体名和模板生成的 XxxColumn
+    names: HashSet<String>,
+    field_types: &'a FieldTypes,
+}
+
+impl Kit for TypeCollector<'_> {}
+
+impl<'a> TypeCollector<'a> {
+    fn new(struct_name: &str, field_types: &'a FieldTypes) -> Self {
+        let names = HashSet::from([struct_name.to_string(), format!("{}Column", struct_name)]);
+        Self {
+            types: vec![],
+            names,
+            field_types,
+        }
+    }
+
+    /// ClickHouse 类型转换为 Rust 类型, 枚举、具名 Tuple 和 Nested 生成名为 type_name 的类型,
+    /// 顶层 Map 使用 HashMap(模板为其生成 serde 转换), 其他位置的 Map 使用 Vec<(K, V)>
+    fn rust_type(
+        &mut self,
+        ty: &ClickhouseType,
+        type_name: &str,
+        column: &str,
+        top: bool,
+    ) -> String {
+        match ty {
+            ClickhouseType::Simple(v) => self.field_types.field_type(v, column).unwrap_or_default(),
+            ClickhouseType::Nullable(v) => {
+                format!("Option<{}>", self.rust_type(v, type_name, column, false))
+            }
+            ClickhouseType::LowCardinality(v) => self.rust_type(v, type_name, column, top),
+            ClickhouseType::Array(v) => {
+                format!("Vec<{}>", self.rust_type(v, type_name, column, false))
+            }
+            ClickhouseType::Map(k, v) => {
+                let k = self.rust_type(k, &format!("{}Key", type_name), column, false);
+                let v = self.rust_type(v, &format!("{}Value", type_name), column, false);
+                if top {
+                    format!("std::collections::HashMap<{}, {}>", k, v)
+                } else {
+                    format!("Vec<({}, {})>", k, v)
+                }
+            }
+            ClickhouseType::Tuple(elements) if elements.iter().all(|v| v.0.is_some()) => {
+                let elements: Vec<(String, ClickhouseType)> = elements
+                    .iter()
+                    .map(|(name, ty)| (name.to_owned().unwrap_or_default(), ty.to_owned()))
+                    .collect();
+                self.struct_type(&elements, type_name, column, &ty_str(ty))
+            }
+            ClickhouseType::Tuple(elements) => {
+                let list: Vec<String> = elements
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (_, v))| {
+                        self.rust_type(v, &format!("{}{}", type_name, i), column, false)
+                    })
+                    .collect();
+                match list.as_slice() {
+                    [v] => format!("({},)", v),
+                    _ => format!("({})", list.join(", ")),
+                }
+            }
+            ClickhouseType::Nested(elements) => {
+                let name = self.struct_type(elements, type_name, column, &ty_str(ty));
+                format!("Vec<{}>", name)
+            }
+            ClickhouseType::Enum(bits, values) => {
+                let mut seen = HashSet::new();
+                let members = values
+                    .iter()
+                    .map(|(label, value)| CustomTypeMember {
+                        name: Self::unique_identifier(pascal_identifier(label), &mut seen, label),
+                        original: label.to_owned(),
+                        value: value.to_string(),
+                    })
+                    .collect();
+                self.push(type_name, &ty_str(ty), Some(format!("i{}", bits)), members)
+            }
+        }
+    }
+
+    /// 具名 Tuple 和 Nested 的元素生成结构体字段
+    fn struct_type(
+        &mut self,
+        elements: &[(String, ClickhouseType)],
+        type_name: &str,
+        column: &str,
+        database_type: &str,
+    ) -> String {
+        let mut seen = HashSet::new();
+        let mut members = vec![];
+        for (name, ty) in elements {
+            let member_type = format!("{}{}", type_name, pascal_identifier(name));
+            members.push(CustomTypeMember {
+                name: Self::unique_identifier(identifier(name), &mut seen, name),
+                original: name.to_owned(),
+                value: self.rust_type(ty, &member_type, column, false),
+            });
+        }
+        self.push(type_name, database_type, None, members)
+    }
+
+    fn push(
+        &mut self,
+        type_name: &str,
+        database_type: &str,
+        repr: Option<String>,
+        members: Vec<CustomTypeMember>,
+    ) -> String {
+        let name = Self::unique_identifier(type_name.to_string(), &mut self.names, type_name);
+        self.types.push(CustomType {
+            name: name.clone(),
+            database_type: database_type.to_string(),
+            repr,
+            members,
+        });
+        name
+    }
+}
+
+/// 类型表达式原文, 用于生成类型的文档注释
+fn ty_str(ty: &ClickhouseType) -> String {
+    match ty {
+        ClickhouseType::Simple(v) => v.to_owned(),
+        ClickhouseType::Nullable(v) => format!("Nullable({})", ty_str(v)),
+        ClickhouseType::LowCardinality(v) => format!("LowCardinality({})", ty_str(v)),
+        ClickhouseType::Array(v) => format!("Array({})", ty_str(v)),
+        ClickhouseType::Map(k, v) => format!("Map({}, {})", ty_str(k), ty_str(v)),
+        ClickhouseType::Tuple(elements) => {
+            let list: Vec<String> = elements
+                .iter()
+                .map(|(name, v)| match name {
+                    Some(name) => format!("{} {}", quote_name(name), ty_str(v)),
+                    None => ty_str(v),
+                })
+                .collect();
+            format!("Tuple({})", list.join(", "))
+        }
+        ClickhouseType::Nested(elements) => {
+            let list: Vec<String> = elements
+                .iter()
+                .map(|(name, v)| format!("{} {}", quote_name(name), ty_str(v)))
+                .collect();
+            format!("Nested({})", list.join(", "))
+        }
+        ClickhouseType::Enum(bits, values) => {
+            let list: Vec<String> = values
+                .iter()
+                .map(|(name, value)| format!("'{}' = {}", name.replace('\'', "\\'"), value))
+                .collect();
+            format!("Enum{}({})", bits, list.join(", "))
+        }
+    }
+}
+
+/// db_url 中解析出的连接信息, url 去掉了用户名、密码、数据库路径和查询参数
+#[derive(Debug, Default, PartialEq)]
+struct ClickhouseUrl {
+    url: String,
+    user: Option<String>,
+    password: Option<String>,
+    database: Option<String>,
+    /// 查询参数, 作为 ClickHouse 设置随每个请求发送
+    options: Vec<(String, String)>,
+}
+
+impl ClickhouseUrl {
+    fn parse(db_url: &str) -> Self {
+        let (scheme, rest) = match db_url.split_once("://") {
+            Some((scheme, rest)) => (format!("{}://", scheme), rest),
+            None => (String::new(), db_url),
+        };
+        let (authority, path) = match rest.find(['/', '?']) {
+            Some(i) => rest.split_at(i),
+            None => (rest, ""),
+        };
+        let (user, password, host) = match authority.rsplit_once('@') {
+            Some((userinfo, host)) => {
+                let (user, password) = match userinfo.split_once(':') {
+                    Some((user, password)) => (user, Some(percent_decode(password))),
+                    None => (userinfo, None),
+                };
+                (Some(percent_decode(user)), password, host)
+            }
+            None => (None, None, authority),
+        };
+        let (database, query) = path.split_once('?').unwrap_or((path, ""));
+        let database = database.trim_matches('/');
+        let options = query
+            .split('&')
+            .filter(|v| !v.is_empty())
+            .map(|v| {
+                let (name, value) = v.split_once('=').unwrap_or((v, ""));
+                (percent_decode(name), percent_decode(value))
+            })
+            .collect();
+        Self {
+            url: format!("{}{}", scheme, host),
+            user: user.filter(|v| !v.is_empty()),
+            password,
+            database: if database.is_empty() {
+                None
+            } else {
+                Some(percent_decode(database))
+            },
+            options,
+        }
+    }
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|v| std::str::from_utf8(v).ok())
+            .and_then(|v| u8::from_str_radix(v, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn quote_name(name: &str) -> String {
+    if name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        name.to_string()
+    } else {
+        format!("`{}`", name)
+    }
+}
+
 const TABLES_SQL: &str = "SELECT name, comment FROM system.tables WHERE database = ? AND engine NOT IN ('System', 'View') ORDER BY name";
+const ENGINE_SQL: &str = "SELECT engine, engine_full, partition_key, sorting_key, primary_key, sampling_key FROM system.tables WHERE database = ? AND name = ?";
 const FIELD_SQL: &str = "SELECT name, type as field_type, comment, default_expression, default_kind, is_in_primary_key, is_in_sorting_key FROM system.columns WHERE database = ? AND table = ? ORDER BY position";
 
 impl GenStruct for ClickhouseImpl {
+    fn config(&self) -> &CustomConfig {
+        &self.config
+    }
+
     async fn get_tables(&self) -> Result<Vec<Table2Comment>> {
-        let database = self.config.schemaname.clone().unwrap_or_default();
+        let database = &self.database;
         let ch_tables: Vec<ClickhouseTable> = self
             .client
             .query(TABLES_SQL)
-            .bind(&database)
+            .bind(database)
             .fetch_all()
             .await
             .map_err(crate::error::Error::Clickhouse)?;
@@ -194,27 +531,42 @@ impl GenStruct for ClickhouseImpl {
         Ok(tables)
     }
 
-    async fn update_type_fields(&self, map: Option<BTreeMap<String, String>>) {
-        if let Some(map) = map {
-            *FIELD_TYPE.write().unwrap() = map;
-        }
-    }
-
-    async fn gen_templates(&self, tables: Vec<Table2Comment>) -> Result<Vec<Table>> {
-        let database = self.config.schemaname.clone().unwrap_or_default();
+    async fn gen_templates(
+        &self,
+        tables: Vec<Table2Comment>,
+        field_types: &FieldTypes,
+    ) -> Result<Vec<Table>> {
+        let database = &self.database;
         let mut templates = vec![];
         for table in tables {
             let ch_fields: Vec<ClickhouseField> = self
                 .client
                 .query(FIELD_SQL)
-                .bind(&database)
+                .bind(database)
                 .bind(&table.table_name)
                 .fetch_all()
                 .await
                 .map_err(crate::error::Error::Clickhouse)?;
-            let fields: Vec<Field> = ch_fields.iter().map(|f| Self::convert_field(f)).collect();
+            let engine = self
+                .client
+                .query(ENGINE_SQL)
+                .bind(database)
+                .bind(&table.table_name)
+                .fetch_optional::<ClickhouseEngine>()
+                .await
+                .map_err(crate::error::Error::Clickhouse)?
+                .map(Self::convert_engine);
             let mut struct_name = table.table_name.clone().to_camel_case();
             struct_name = Self::first_char_to_uppercase(&struct_name);
+            let type_prefix = pascal_identifier(&table.table_name);
+            let mut collector = TypeCollector::new(&type_prefix, field_types);
+            let fields: Vec<Field> = ch_fields
+                .iter()
+                .map(|v| {
+                    let type_name = format!("{}{}", type_prefix, pascal_identifier(&v.name));
+                    Self::convert_field(&table.table_name, v, &type_name, &mut collector)
+                })
+                .collect();
             let (index_key, unique_key) = Self::build_keys(&ch_fields);
             let table = Table {
                 table_name: table.table_name.to_owned(),
@@ -222,7 +574,17 @@ impl GenStruct for ClickhouseImpl {
                 fields,
                 comment: table.table_comment.unwrap_or_default(),
                 index_key,
+                primary_key: unique_key.first().cloned().unwrap_or_default(),
                 unique_key,
+                foreign_keys: vec![],
+                soft_delete: None,
+                module_name: String::new(),
+                without_rowid: false,
+                rowid_alias: None,
+                custom_types: collector.types,
+                engine,
+                time_precision: None,
+                kind: None,
             };
             templates.push(table);
         }
@@ -344,9 +706,13 @@ mod tests {
 
     // ========== FIELD_TYPE mapping tests ==========
 
+    fn field_types() -> FieldTypes {
+        FieldTypes::new(FIELD_TYPE.clone().into())
+    }
+
     #[test]
     fn test_field_type_integers() {
-        let map = FIELD_TYPE.read().unwrap();
+        let map = field_types();
         let cases = vec![
             ("Int8", "i8"),
             ("Int16", "i16"),
@@ -362,136 +728,100 @@ mod tests {
             ("UInt256", "String"),
         ];
         for (ch_type, expected_rust) in cases {
-            let result =
-                ClickhouseImpl::get_field_type(ch_type, "test_field", &map).unwrap();
+            let result = map.field_type(ch_type, "test_field").unwrap();
             assert_eq!(result, expected_rust, "type mapping failed for {}", ch_type);
         }
     }
 
     #[test]
     fn test_field_type_floats() {
-        let map = FIELD_TYPE.read().unwrap();
-        assert_eq!(
-            ClickhouseImpl::get_field_type("Float32", "f", &map).unwrap(),
-            "f32"
-        );
-        assert_eq!(
-            ClickhouseImpl::get_field_type("Float64", "f", &map).unwrap(),
-            "f64"
-        );
+        let map = field_types();
+        assert_eq!(map.field_type("Float32", "f").unwrap(), "f32");
+        assert_eq!(map.field_type("Float64", "f").unwrap(), "f64");
     }
 
     #[test]
     fn test_field_type_string_types() {
-        let map = FIELD_TYPE.read().unwrap();
-        assert_eq!(
-            ClickhouseImpl::get_field_type("String", "f", &map).unwrap(),
-            "String"
-        );
-        assert_eq!(
-            ClickhouseImpl::get_field_type("FixedString(128)", "f", &map).unwrap(),
-            "String"
-        );
+        let map = field_types();
+        assert_eq!(map.field_type("String", "f").unwrap(), "String");
+        assert_eq!(map.field_type("FixedString(128)", "f").unwrap(), "String");
     }
 
     #[test]
     fn test_field_type_date_time() {
-        let map = FIELD_TYPE.read().unwrap();
-        assert_eq!(
-            ClickhouseImpl::get_field_type("Date", "f", &map).unwrap(),
-            "chrono::NaiveDate"
-        );
-        assert_eq!(
-            ClickhouseImpl::get_field_type("Date32", "f", &map).unwrap(),
-            "chrono::NaiveDate"
-        );
+        let map = field_types();
+        assert_eq!(map.field_type("Date", "f").unwrap(), "chrono::NaiveDate");
+        assert_eq!(map.field_type("Date32", "f").unwrap(), "chrono::NaiveDate");
         assert_eq!(
-            ClickhouseImpl::get_field_type("DateTime", "f", &map).unwrap(),
+            map.field_type("DateTime", "f").unwrap(),
             "chrono::NaiveDateTime"
         );
         assert_eq!(
-            ClickhouseImpl::get_field_type("DateTime('UTC')", "f", &map).unwrap(),
+            map.field_type("DateTime('UTC')", "f").unwrap(),
             "chrono::NaiveDateTime"
         );
         assert_eq!(
-            ClickhouseImpl::get_field_type("DateTime64(3)", "f", &map).unwrap(),
+            map.field_type("DateTime64(3)", "f").unwrap(),
             "chrono::NaiveDateTime"
         );
     }
 
     #[test]
     fn test_field_type_bool() {
-        let map = FIELD_TYPE.read().unwrap();
-        assert_eq!(
-            ClickhouseImpl::get_field_type("Bool", "f", &map).unwrap(),
-            "bool"
-        );
+        let map = field_types();
+        assert_eq!(map.field_type("Bool", "f").unwrap(), "bool");
     }
 
     #[test]
     fn test_field_type_special_types() {
-        let map = FIELD_TYPE.read().unwrap();
-        assert_eq!(
-            ClickhouseImpl::get_field_type("UUID", "f", &map).unwrap(),
-            "String"
-        );
-        assert_eq!(
-            ClickhouseImpl::get_field_type("IPv4", "f", &map).unwrap(),
-            "String"
-        );
-        assert_eq!(
-            ClickhouseImpl::get_field_type("IPv6", "f", &map).unwrap(),
-            "String"
-        );
-        assert_eq!(
-            ClickhouseImpl::get_field_type("JSON", "f", &map).unwrap(),
-            "serde_json::Value"
-        );
+        let map = field_types();
+        assert_eq!(map.field_type("UUID", "f").unwrap(), "String");
+        assert_eq!(map.field_type("IPv4", "f").unwrap(), "String");
+        assert_eq!(map.field_type("IPv6", "f").unwrap(), "String");
+        assert_eq!(map.field_type("JSON", "f").unwrap(), "serde_json::Value");
     }
 
     #[test]
     fn test_field_type_complex_types() {
-        let map = FIELD_TYPE.read().unwrap();
+        let map = field_types();
+        assert_eq!(map.field_type("Array(Int32)", "f").unwrap(), "String");
         assert_eq!(
-            ClickhouseImpl::get_field_type("Array(Int32)", "f", &map).unwrap(),
+            map.field_type("Map(String, UInt64)", "f").unwrap(),
             "String"
         );
         assert_eq!(
-            ClickhouseImpl::get_field_type("Map(String, UInt64)", "f", &map).unwrap(),
+            map.field_type("Tuple(String, Int32)", "f").unwrap(),
             "String"
         );
         assert_eq!(
-            ClickhouseImpl::get_field_type("Tuple(String, Int32)", "f", &map).unwrap(),
-            "String"
-        );
-        assert_eq!(
-            ClickhouseImpl::get_field_type("Enum8('a' = 1, 'b' = 2)", "f", &map).unwrap(),
+            map.field_type("Enum8('a' = 1, 'b' = 2)", "f").unwrap(),
             "String"
         );
     }
 
     #[test]
     fn test_field_type_decimal_variants() {
-        let map = FIELD_TYPE.read().unwrap();
-        assert_eq!(
-            ClickhouseImpl::get_field_type("Decimal(10, 2)", "f", &map).unwrap(),
-            "String"
-        );
-        assert_eq!(
-            ClickhouseImpl::get_field_type("Decimal32(4)", "f", &map).unwrap(),
-            "String"
-        );
+        let map = field_types();
+        assert_eq!(map.field_type("Decimal(10, 2)", "f").unwrap(), "String");
+        assert_eq!(map.field_type("Decimal32(4)", "f").unwrap(), "String");
+        assert_eq!(map.field_type("Decimal128(8)", "f").unwrap(), "String");
+    }
+
+    #[test]
+    fn test_field_type_unmapped_is_recorded() {
+        let map = field_types();
         assert_eq!(
-            ClickhouseImpl::get_field_type("Decimal128(8)", "f", &map).unwrap(),
+            map.field_type("Point", "places.location").unwrap(),
             "String"
         );
+        assert_eq!(map.unmapped(), ["places.location: Point"]);
     }
 
     // ========== convert_field tests ==========
 
-    /// Initialize the LANGUAGE global for tests
-    async fn init_language() {
-        let _ = crate::keywords::get_or_init("rs").await;
+    fn convert(ch: &ClickhouseField) -> Field {
+        let field_types = field_types();
+        ClickhouseImpl::convert_field("t", ch, "T", &mut TypeCollector::new("T", &field_types))
     }
 
     fn make_ch_field(name: &str, field_type: &str) -> ClickhouseField {
@@ -506,11 +836,10 @@ mod tests {
         }
     }
 
-    #[tokio::test]
-    async fn test_convert_field_basic_int() {
-        init_language().await;
+    #[test]
+    fn test_convert_field_basic_int() {
         let ch = make_ch_field("user_id", "UInt64");
-        let field = ClickhouseImpl::convert_field(&ch);
+        let field = convert(&ch);
         assert_eq!(field.field_name, "user_id");
         assert_eq!(field.field_type, "u64");
         assert_eq!(field.FieldName, "UserId");
@@ -519,29 +848,26 @@ mod tests {
         assert_eq!(field.is_null, 0);
     }
 
-    #[tokio::test]
-    async fn test_convert_field_nullable() {
-        init_language().await;
+    #[test]
+    fn test_convert_field_nullable() {
         let ch = make_ch_field("email", "Nullable(String)");
-        let field = ClickhouseImpl::convert_field(&ch);
+        let field = convert(&ch);
         assert_eq!(field.field_name, "email");
         assert_eq!(field.field_type, "String");
         assert_eq!(field.is_null, 1);
         assert_eq!(field.database_field_type, "Nullable(String)");
     }
 
-    #[tokio::test]
-    async fn test_convert_field_low_cardinality_nullable() {
-        init_language().await;
+    #[test]
+    fn test_convert_field_low_cardinality_nullable() {
         let ch = make_ch_field("status", "LowCardinality(Nullable(String))");
-        let field = ClickhouseImpl::convert_field(&ch);
+        let field = convert(&ch);
         assert_eq!(field.field_type, "String");
         assert_eq!(field.is_null, 1);
     }
 
-    #[tokio::test]
-    async fn test_convert_field_with_comment_and_default() {
-        init_language().await;
+    #[test]
+    fn test_convert_field_with_comment_and_default() {
         let ch = ClickhouseField {
             name: "created_at".to_string(),
             field_type: "DateTime".to_string(),
@@ -551,20 +877,152 @@ mod tests {
             is_in_primary_key: 0,
             is_in_sorting_key: 0,
         };
-        let field = ClickhouseImpl::convert_field(&ch);
+        let field = convert(&ch);
         assert_eq!(field.comment, "creation time");
         assert_eq!(field.default, Some("now()".to_string()));
         assert_eq!(field.field_type, "chrono::NaiveDateTime");
+        assert_eq!(field.default_kind, Some("DEFAULT".to_string()));
+        assert!(!field.generated);
+    }
+
+    #[test]
+    fn test_convert_field_generated() {
+        for (default_kind, generated) in [
+            ("MATERIALIZED", true),
+            ("ALIAS", true),
+            ("EPHEMERAL", false),
+        ] {
+            let mut ch = make_ch_field("day", "Date");
+            ch.default_expression = "toDate(created_at)".to_string();
+            ch.default_kind = default_kind.to_string();
+            let field = convert(&ch);
+            assert_eq!(field.default_kind, Some(default_kind.to_string()));
+            assert_eq!(field.generated, generated);
+        }
+    }
+
+    #[test]
+    fn test_parse_url() {
+        assert_eq!(
+            ClickhouseUrl::parse("http://localhost:8123"),
+            ClickhouseUrl {
+                url: "http://localhost:8123".to_string(),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            ClickhouseUrl::parse("https://reader:p%40ss:word@ch.example.com:8443/analytics/"),
+            ClickhouseUrl {
+                url: "https://ch.example.com:8443".to_string(),
+                user: Some("reader".to_string()),
+                password: Some("p@ss:word".to_string()),
+                database: Some("analytics".to_string()),
+                options: vec![],
+            }
+        );
+        assert_eq!(
+            ClickhouseUrl::parse("http://default@localhost:8123?max_execution_time=30"),
+            ClickhouseUrl {
+                url: "http://localhost:8123".to_string(),
+                user: Some("default".to_string()),
+                options: vec![("max_execution_time".to_string(), "30".to_string())],
+                ..Default::default()
+            }
+        );
     }
 
-    #[tokio::test]
-    async fn test_convert_field_empty_default() {
-        init_language().await;
+    #[test]
+    fn test_table_ttl() {
+        assert_eq!(
+            ClickhouseImpl::table_ttl(
+                "MergeTree PARTITION BY toYYYYMM(d) ORDER BY id TTL d + toIntervalDay(7) SETTINGS index_granularity = 8192"
+            ),
+            "d + toIntervalDay(7)"
+        );
+        assert_eq!(
+            ClickhouseImpl::table_ttl("MergeTree ORDER BY id TTL d + toIntervalMonth(1) DELETE"),
+            "d + toIntervalMonth(1) DELETE"
+        );
+        assert_eq!(
+            ClickhouseImpl::table_ttl("MergeTree ORDER BY id SETTINGS index_granularity = 8192"),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_convert_field_empty_default() {
         let ch = make_ch_field("name", "String");
-        let field = ClickhouseImpl::convert_field(&ch);
+        let field = convert(&ch);
         assert_eq!(field.default, None);
     }
 
+    #[test]
+    fn test_convert_field_complex_types() {
+        let field_types = field_types();
+        let mut collector = TypeCollector::new("Events", &field_types);
+        let cases = [
+            ("tags", "Array(LowCardinality(String))", "Vec<String>"),
+            (
+                "attrs",
+                "Map(String, Array(Nullable(UInt64)))",
+                "std::collections::HashMap<String, Vec<Option<u64>>>",
+            ),
+            (
+                "pairs",
+                "Array(Map(String, Int32))",
+                "Vec<Vec<(String, i32)>>",
+            ),
+            ("point", "Tuple(Float64, Float64)", "(f64, f64)"),
+            (
+                "status",
+                "Enum8('active' = 1, 'in-active' = -1)",
+                "EventsStatus",
+            ),
+            (
+                "user",
+                "Tuple(id UInt64, `type` Enum16('a' = 1000))",
+                "EventsUser",
+            ),
+            (
+                "items",
+                "Nested(sku String, qty UInt32)",
+                "Vec<EventsItems>",
+            ),
+            ("column", "Enum8('x' = 1)", "EventsColumn2"),
+        ];
+        for (name, field_type, expected) in cases {
+            let ch = make_ch_field(name, field_type);
+            let type_name = format!("Events{}", pascal_identifier(name));
+            let field = ClickhouseImpl::convert_field("events", &ch, &type_name, &mut collector);
+            assert_eq!(
+                field.field_type, expected,
+                "type mapping failed for {}",
+                field_type
+            );
+        }
+        let names: Vec<&str> = collector.types.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "EventsStatus",
+                "EventsUserType",
+                "EventsUser",
+                "EventsItems",
+                "EventsColumn2"
+            ]
+        );
+        let status = &collector.types[0];
+        assert_eq!(status.repr.as_deref(), Some("i8"));
+        assert_eq!(status.members[1].name, "InActive");
+        assert_eq!(status.members[1].original, "in-active");
+        assert_eq!(status.members[1].value, "-1");
+        let user = &collector.types[2];
+        assert_eq!(user.repr, None);
+        // 保留字在 GenStruct::run 中转义
+        assert_eq!(user.members[1].name, "type");
+        assert_eq!(user.members[1].value, "EventsUserType");
+    }
+
     // ========== build_keys tests ==========
 
     fn make_key_field(name: &str, primary: u8, sorting: u8) -> ClickhouseField {
@@ -0,0 +1,181 @@
+/// ClickHouse 类型表达式, 例如 Array(Nullable(String)), Map(String, UInt64), Enum8('a' = 1)
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClickhouseType {
+    /// 基础类型, 带参数的基础类型保留原文, 例如 Int32, DateTime('UTC'), Decimal(10, 2)
+    Simple(String),
+    Nullable(Box<ClickhouseType>),
+    LowCardinality(Box<ClickhouseType>),
+    Array(Box<ClickhouseType>),
+    Map(Box<ClickhouseType>, Box<ClickhouseType>),
+    /// 元素名为 None 时是普通 Tuple
+    Tuple(Vec<(Option<String>, ClickhouseType)>),
+    /// 未展开的 Nested(flatten_nested = 0), 展开时每个子字段是独立的 `n.a Array(T)` 字段
+    Nested(Vec<(String, ClickhouseType)>),
+    /// 底层整数位数(8/16)和 (名称, 取值)
+    Enum(u8, Vec<(String, i64)>),
+}
+
+impl ClickhouseType {
+    /// 解析类型表达式, 格式不正确时返回 None
+    pub fn parse(type_str: &str) -> Option<Self> {
+        let type_str = type_str.trim();
+        let Some((name, args)) = type_str.split_once('(') else {
+            return Some(ClickhouseType::Simple(type_str.to_string()));
+        };
+        let args = split_args(args.strip_suffix(')')?)?;
+        let ty = match (name.trim(), args.as_slice()) {
+            ("Nullable", [inner]) => ClickhouseType::Nullable(Box::new(Self::parse(inner)?)),
+            ("LowCardinality", [inner]) => {
+                ClickhouseType::LowCardinality(Box::new(Self::parse(inner)?))
+            }
+            ("Array", [inner]) => ClickhouseType::Array(Box::new(Self::parse(inner)?)),
+            ("Map", [key, value]) => {
+                ClickhouseType::Map(Box::new(Self::parse(key)?), Box::new(Self::parse(value)?))
+            }
+            ("Tuple", elements) => {
+                let mut list = vec![];
+                for element in elements {
+                    list.push(match split_name(element) {
+                        Some((name, ty)) => (Some(name), Self::parse(ty)?),
+                        None => (None, Self::parse(element)?),
+                    });
+                }
+                ClickhouseType::Tuple(list)
+            }
+            ("Nested", elements) => {
+                let mut list = vec![];
+                for element in elements {
+                    let (name, ty) = split_name(element)?;
+                    list.push((name, Self::parse(ty)?));
+                }
+                ClickhouseType::Nested(list)
+            }
+            ("Enum8", values) => ClickhouseType::Enum(8, parse_enum(values)?),
+            ("Enum16", values) => ClickhouseType::Enum(16, parse_enum(values)?),
+            ("Nullable" | "LowCardinality" | "Array" | "Map", _) => return None,
+            _ => ClickhouseType::Simple(type_str.to_string()),
+        };
+        Some(ty)
+    }
+}
+
+/// 按顶层逗号拆分参数, 忽略括号和单引号字符串中的逗号
+fn split_args(args: &str) -> Option<Vec<&str>> {
+    let mut list = vec![];
+    let mut depth = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    let mut start = 0;
+    for (i, c) in args.char_indices() {
+        if quoted {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '\'' => quoted = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '\'' => quoted = true,
+            '(' => depth += 1,
+            ')' if depth == 0 => return None,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                list.push(args[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 || quoted {
+        return None;
+    }
+    list.push(args[start..].trim());
+    Some(list)
+}
+
+/// 拆分具名元素 `name Type`, 元素名可以用反引号包裹
+fn split_name(element: &str) -> Option<(String, &str)> {
+    if let Some(rest) = element.strip_prefix('`') {
+        let (name, ty) = rest.split_once('`')?;
+        return Some((name.to_string(), ty.trim()));
+    }
+    let (name, ty) = element.split_once(char::is_whitespace)?;
+    if name.contains('(') || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+    Some((name.to_string(), ty.trim()))
+}
+
+/// 解析枚举值 `'name' = 1`
+fn parse_enum(values: &[&str]) -> Option<Vec<(String, i64)>> {
+    let mut list = vec![];
+    for value in values {
+        let (name, number) = value.rsplit_once('=')?;
+        let name = name.trim().strip_prefix('\'')?.strip_suffix('\'')?;
+        let name = name.replace("\\'", "'").replace("\\\\", "\\");
+        list.push((name, number.trim().parse().ok()?));
+    }
+    Some(list)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ClickhouseType::*;
+
+    fn simple(name: &str) -> ClickhouseType {
+        Simple(name.to_string())
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(ClickhouseType::parse("Int32"), Some(simple("Int32")));
+        assert_eq!(
+            ClickhouseType::parse("DateTime64(3, 'Asia/Shanghai')"),
+            Some(simple("DateTime64(3, 'Asia/Shanghai')"))
+        );
+        assert_eq!(
+            ClickhouseType::parse("Array(Nullable(String))"),
+            Some(Array(Box::new(Nullable(Box::new(simple("String"))))))
+        );
+        assert_eq!(
+            ClickhouseType::parse("Map(LowCardinality(String), Decimal(10, 2))"),
+            Some(Map(
+                Box::new(LowCardinality(Box::new(simple("String")))),
+                Box::new(simple("Decimal(10, 2)"))
+            ))
+        );
+        assert_eq!(
+            ClickhouseType::parse("Tuple(String, UInt8)"),
+            Some(Tuple(vec![
+                (None, simple("String")),
+                (None, simple("UInt8"))
+            ]))
+        );
+        assert_eq!(
+            ClickhouseType::parse("Tuple(a String, `b c` DateTime('UTC'))"),
+            Some(Tuple(vec![
+                (Some("a".to_string()), simple("String")),
+                (Some("b c".to_string()), simple("DateTime('UTC')"))
+            ]))
+        );
+        assert_eq!(
+            ClickhouseType::parse("Nested(id UInt64, tags Array(String))"),
+            Some(Nested(vec![
+                ("id".to_string(), simple("UInt64")),
+                ("tags".to_string(), Array(Box::new(simple("String"))))
+            ]))
+        );
+        assert_eq!(
+            ClickhouseType::parse(r"Enum8('a' = 1, 'b, \'c\'' = -2)"),
+            Some(Enum(
+                8,
+                vec![("a".to_string(), 1), ("b, 'c'".to_string(), -2)]
+            ))
+        );
+        assert_eq!(ClickhouseType::parse("Array(String"), None);
+        assert_eq!(ClickhouseType::parse("Map(String)"), None);
+    }
+}
@@ -1,60 +1,50 @@
 use crate::error::Result;
-use crate::reverse_impl::{clickhouse_impl, mysql_impl, postgres_impl, sqlite_impl, tdengine_impl};
+#[cfg(feature = "clickhouse")]
+use crate::reverse_impl::clickhouse_impl;
+#[cfg(feature = "mysql")]
+use crate::reverse_impl::mysql_impl;
+#[cfg(feature = "postgres")]
+use crate::reverse_impl::postgres_impl;
+#[cfg(feature = "sqlite")]
+use crate::reverse_impl::sqlite_impl;
+#[cfg(feature = "tdengine")]
+use crate::reverse_impl::tdengine_impl;
+use std::collections::BTreeMap;
 use tokio::io::AsyncWriteExt;
 
+/// 导出已启用数据库的默认字段类型映射
 pub async fn export() -> Result<()> {
-    let mysql_default = mysql_impl::FIELD_TYPE.read().unwrap().clone();
-    let postgres_default = postgres_impl::FIELD_TYPE.read().unwrap().clone();
-    let sqlite_default = sqlite_impl::FIELD_TYPE.read().unwrap().clone();
-    let mysql_default = format!("{:#?}", mysql_default);
-    let postgres_default = format!("{:#?}", postgres_default);
-    let sqlite_default = format!("{:#?}", sqlite_default);
-    let mut fs = tokio::fs::File::options()
-        .create(true)
-        .truncate(true)
-        .write(true)
-        .open("./default_mysql.json")
-        .await?;
-    fs.write_all(mysql_default.replace(",\n}", "\n}").as_bytes())
-        .await?;
-    let mut fs = tokio::fs::File::options()
-        .create(true)
-        .truncate(true)
-        .write(true)
-        .open("./default_postgres.json")
-        .await?;
-    fs.write_all(postgres_default.replace(",\n}", "\n}").as_bytes())
-        .await?;
-
-    let mut fs = tokio::fs::File::options()
-        .create(true)
-        .truncate(true)
-        .write(true)
-        .open("./default_sqlite.json")
-        .await?;
-    fs.write_all(sqlite_default.replace(",\n}", "\n}").as_bytes())
-        .await?;
-
-    let clickhouse_default = clickhouse_impl::FIELD_TYPE.read().unwrap().clone();
-    let clickhouse_default = format!("{:#?}", clickhouse_default);
-    let mut fs = tokio::fs::File::options()
-        .create(true)
-        .truncate(true)
-        .write(true)
-        .open("./default_clickhouse.json")
-        .await?;
-    fs.write_all(clickhouse_default.replace(",\n}", "\n}").as_bytes())
-        .await?;
+    #[cfg(feature = "mysql")]
+    {
+        write_default("./default_mysql.json", &mysql_impl::FIELD_TYPE).await?;
+    }
+    #[cfg(feature = "postgres")]
+    {
+        write_default("./default_postgres.json", &postgres_impl::FIELD_TYPE).await?;
+    }
+    #[cfg(feature = "sqlite")]
+    {
+        write_default("./default_sqlite.json", &sqlite_impl::FIELD_TYPE).await?;
+    }
+    #[cfg(feature = "clickhouse")]
+    {
+        write_default("./default_clickhouse.json", &clickhouse_impl::FIELD_TYPE).await?;
+    }
+    #[cfg(feature = "tdengine")]
+    {
+        write_default("./default_tdengine.json", &tdengine_impl::FIELD_TYPE).await?;
+    }
+    Ok(())
+}
 
-    let tdengine_default = tdengine_impl::FIELD_TYPE.read().unwrap().clone();
-    let tdengine_default = format!("{:#?}", tdengine_default);
+async fn write_default(path: &str, field_type: &BTreeMap<String, String>) -> Result<()> {
+    let data = format!("{:#?}", field_type);
     let mut fs = tokio::fs::File::options()
         .create(true)
         .truncate(true)
         .write(true)
-        .open("./default_tdengine.json")
-        .await?;
-    fs.write_all(tdengine_default.replace(",\n}", "\n}").as_bytes())
+        .open(path)
         .await?;
+    fs.write_all(data.replace(",\n}", "\n}").as_bytes()).await?;
     Ok(())
 }
@@ -1,31 +1,70 @@
-use crate::error::Result;
-use crate::table::{Table, Table2Comment};
-use std::collections::BTreeMap;
+use crate::error::{Error, Result};
+#[cfg(feature = "clickhouse")]
+use crate::reverse_impl::clickhouse_impl::ClickhouseImpl;
+use crate::reverse_impl::common::CustomConfig;
+#[cfg(feature = "mysql")]
+use crate::reverse_impl::mysql_impl::MysqlImpl;
+#[cfg(feature = "postgres")]
+use crate::reverse_impl::postgres_impl::PostgresImpl;
+#[cfg(feature = "sqlite")]
+use crate::reverse_impl::sqlite_impl::SqliteImpl;
+#[cfg(feature = "tdengine")]
+use crate::reverse_impl::tdengine_impl::TdengineImpl;
+use crate::reverse_impl::type_map::FieldTypes;
+use crate::table::{ForeignKey, SoftDelete, Table, Table2Comment};
+use crate::template::diesel;
+use crate::template::kit::Kit;
+use crate::template::render::Renderer;
+use crate::template::template_type::{Target, TemplateType};
+use std::future::Future;
+use std::pin::Pin;
 
-pub trait GenStruct {
-    async fn run(&self, filename: &str) -> Result<Vec<Table>> {
+/// 读取表结构并转换为模板使用的 [`Table`], 每个数据库一个实现
+///
+/// 数据库以外的实现只需要实现 config/get_tables/gen_templates/index_key,
+/// run 按 [`Renderer`] 的类型映射设置字段类型, 结构体名和字段名在 [`Renderer::render`] 中生成
+#[allow(async_fn_in_trait)]
+pub trait GenStruct: Kit {
+    /// 读取表结构, renderer 或配置文件中的 strict 为 true 时存在未匹配的字段类型会报错
+    async fn run(&self, renderer: &Renderer) -> Result<Vec<Table>> {
         let tables = self.get_tables().await?;
-        let fields_type = self.load_custom_fields_type(filename).await?;
-        self.update_type_fields(fields_type).await;
-        let templates = self.gen_templates(tables).await?;
+        let field_types = renderer.field_types();
+        let mut templates = self.gen_templates(tables, &field_types).await?;
+        let soft_delete = self.config().soft_delete.to_owned().unwrap_or_default();
+        for table in templates.iter_mut() {
+            table.soft_delete =
+                SoftDelete::new(&soft_delete, &table.fields, &renderer.template_type);
+        }
+        if renderer.effective_target() == Target::Diesel {
+            diesel::check_sql_types(&templates, renderer.template_type, &field_types);
+        }
+        let strict = renderer.strict || self.config().strict.unwrap_or_default();
+        Self::check_unmapped_field_types(field_types.unmapped(), strict)?;
         Ok(templates)
     }
 
-    async fn load_custom_fields_type(
-        &self,
-        filename: &str,
-    ) -> Result<Option<BTreeMap<String, String>>> {
-        if filename.is_empty() {
-            return Ok(None);
+    /// 未匹配的字段类型使用 String 并提示, 严格模式下汇总报错
+    fn check_unmapped_field_types(list: Vec<String>, strict: bool) -> Result<()> {
+        if strict && !list.is_empty() {
+            return Err(Error::UnmappedFieldTypes(list));
         }
-        let s = tokio::fs::read_to_string(filename).await?;
-        let fields_type: BTreeMap<String, String> = serde_json::from_str(&s)?;
-        Ok(Some(fields_type))
+        for item in list {
+            Self::write_red(&format!(
+                "{} field type does not match, default type <String> will be used",
+                item
+            ))?;
+        }
+        Ok(())
     }
 
+    fn config(&self) -> &CustomConfig;
     async fn get_tables(&self) -> Result<Vec<Table2Comment>>;
-    async fn update_type_fields(&self, map: Option<BTreeMap<String, String>>);
-    async fn gen_templates(&self, tables: Vec<Table2Comment>) -> Result<Vec<Table>>;
+    /// 读取字段并按 field_types 映射字段类型
+    async fn gen_templates(
+        &self,
+        tables: Vec<Table2Comment>,
+        field_types: &FieldTypes,
+    ) -> Result<Vec<Table>>;
 
     async fn filter_tables(
         &self,
@@ -59,5 +98,87 @@ pub trait GenStruct {
         }
     }
 
+    #[cfg_attr(
+        not(any(feature = "mysql", feature = "postgres", feature = "sqlite")),
+        allow(dead_code)
+    )]
     async fn index_key(&self, table_name: &str) -> Result<(Vec<Vec<String>>, Vec<Vec<String>>)>;
+
+    /// 主键字段, 按主键中的顺序返回
+    #[cfg_attr(
+        not(any(feature = "mysql", feature = "postgres", feature = "sqlite")),
+        allow(dead_code)
+    )]
+    async fn primary_key(&self, _table_name: &str) -> Result<Vec<String>> {
+        Ok(vec![])
+    }
+
+    /// 外键, 复合外键每个字段一条
+    #[cfg_attr(
+        not(any(feature = "mysql", feature = "postgres", feature = "sqlite")),
+        allow(dead_code)
+    )]
+    async fn foreign_keys(&self, _table_name: &str) -> Result<Vec<ForeignKey>> {
+        Ok(vec![])
+    }
+}
+
+/// [`DynGenStruct`] 返回的 Future
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// [`GenStruct`] 的 async fn 不能用作 trait 对象, DynGenStruct 把 run 的结果装箱,
+/// 运行时按数据库类型选择实现时使用 `Box<dyn DynGenStruct>`
+pub trait DynGenStruct: Send + Sync {
+    /// 数据库配置
+    fn config(&self) -> &CustomConfig;
+    /// 同 [`GenStruct::run`], 方法名不同以免同时导入两个 trait 时调用有歧义
+    fn run_boxed<'a>(&'a self, renderer: &'a Renderer) -> BoxFuture<'a, Result<Vec<Table>>>;
+}
+
+macro_rules! dyn_gen_struct {
+    ($($(#[$meta:meta])* $name:ty;)*) => {$(
+        $(#[$meta])*
+        impl DynGenStruct for $name {
+            fn config(&self) -> &CustomConfig {
+                GenStruct::config(self)
+            }
+
+            fn run_boxed<'a>(&'a self, renderer: &'a Renderer) -> BoxFuture<'a, Result<Vec<Table>>> {
+                Box::pin(GenStruct::run(self, renderer))
+            }
+        }
+    )*};
+}
+
+dyn_gen_struct! {
+    #[cfg(feature = "mysql")]
+    MysqlImpl;
+    #[cfg(feature = "postgres")]
+    PostgresImpl;
+    #[cfg(feature = "sqlite")]
+    SqliteImpl;
+    #[cfg(feature = "clickhouse")]
+    ClickhouseImpl;
+    #[cfg(feature = "tdengine")]
+    TdengineImpl;
+}
+
+/// 按数据库类型连接数据库
+pub async fn connect(
+    template_type: TemplateType,
+    config: CustomConfig,
+) -> Result<Box<dyn DynGenStruct>> {
+    let db: Box<dyn DynGenStruct> = match template_type {
+        #[cfg(feature = "mysql")]
+        TemplateType::Mysql => Box::new(MysqlImpl::init(config).await?),
+        #[cfg(feature = "postgres")]
+        TemplateType::Postgres => Box::new(PostgresImpl::init(config).await?),
+        #[cfg(feature = "sqlite")]
+        TemplateType::Sqlite => Box::new(SqliteImpl::init(config).await?),
+        #[cfg(feature = "clickhouse")]
+        TemplateType::Clickhouse => Box::new(ClickhouseImpl::init(config).await?),
+        #[cfg(feature = "tdengine")]
+        TemplateType::Tdengine => Box::new(TdengineImpl::init(config).await?),
+    };
+    Ok(db)
 }
@@ -1,8 +1,16 @@
+#[cfg(feature = "clickhouse")]
 pub mod clickhouse_impl;
+#[cfg(feature = "clickhouse")]
+pub mod clickhouse_type;
 pub mod common;
 pub mod export;
 pub mod gen_struct;
+#[cfg(feature = "mysql")]
 pub mod mysql_impl;
+#[cfg(feature = "postgres")]
 pub mod postgres_impl;
+#[cfg(feature = "sqlite")]
 pub mod sqlite_impl;
+#[cfg(feature = "tdengine")]
 pub mod tdengine_impl;
+pub mod type_map;
@@ -1,16 +1,17 @@
 use crate::error::Result;
 use crate::reverse_impl::common::CustomConfig;
 use crate::reverse_impl::gen_struct::GenStruct;
-use crate::table::{Field, Table, Table2Comment};
+use crate::reverse_impl::type_map::FieldTypes;
+use crate::table::{Field, ForeignKey, Table, Table2Comment};
 use crate::template::kit::Kit;
 use fn_macro::{btreemap, if_else};
 use inflector::Inflector;
 use sqlx::Row;
 use std::collections::BTreeMap;
-use std::sync::{LazyLock, RwLock};
+use std::sync::LazyLock;
 
-pub static FIELD_TYPE: LazyLock<RwLock<BTreeMap<String, String>>> = LazyLock::new(|| {
-    let map = btreemap!(
+pub static FIELD_TYPE: LazyLock<BTreeMap<String, String>> = LazyLock::new(|| {
+    btreemap!(
         "^varbinary\\(\\d+\\)$".to_string() => "Vec<u8>".to_string(),
         "^binary\\(\\d+\\)$".to_string() => "Vec<u8>".to_string(),
         "^bigint unsigned$".to_string() => "u64".to_string(),
@@ -51,8 +52,7 @@ pub static FIELD_TYPE: LazyLock<RwLock<BTreeMap<String, String>>> = LazyLock::ne
         "char".to_string() => "String".to_string(),
         "text".to_string() => "String".to_string(),
         "year".to_string() => "Year".to_string()
-    );
-    RwLock::new(map)
+    )
 });
 
 #[derive(Debug, Clone)]
@@ -74,8 +74,18 @@ const FIELD_SQL:&str = "SELECT CAST(COLUMN_NAME as CHAR ) as field_name, CAST(CO
 FROM INFORMATION_SCHEMA.COLUMNS
 WHERE table_schema = DATABASE() AND table_name = ? ORDER BY ORDINAL_POSITION";
 const TABLES_SQL: &str = "SELECT CAST(TABLE_NAME AS CHAR) as table_name, CAST(TABLE_COMMENT as CHAR) as table_comment FROM INFORMATION_SCHEMA.TABLES WHERE TABLE_SCHEMA = DATABASE()";
+const PRIMARY_KEY_SQL: &str = "SELECT CAST(COLUMN_NAME AS CHAR) as column_name FROM INFORMATION_SCHEMA.KEY_COLUMN_USAGE
+WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ? AND CONSTRAINT_NAME = 'PRIMARY' ORDER BY ORDINAL_POSITION";
+const FOREIGN_KEY_SQL: &str = "SELECT CAST(COLUMN_NAME AS CHAR) as column_name, CAST(REFERENCED_TABLE_NAME AS CHAR) as referenced_table,
+       CAST(REFERENCED_COLUMN_NAME AS CHAR) as referenced_column
+FROM INFORMATION_SCHEMA.KEY_COLUMN_USAGE
+WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ? AND REFERENCED_TABLE_NAME IS NOT NULL ORDER BY CONSTRAINT_NAME, ORDINAL_POSITION";
 
 impl GenStruct for MysqlImpl {
+    fn config(&self) -> &CustomConfig {
+        &self.config
+    }
+
     async fn get_tables(&self) -> Result<Vec<Table2Comment>> {
         let mut pool = self.pool.acquire().await?;
         let mut tables = sqlx::query_as::<_, Table2Comment>(TABLES_SQL)
@@ -90,20 +100,19 @@ impl GenStruct for MysqlImpl {
         Ok(tables)
     }
 
-    async fn update_type_fields(&self, map: Option<BTreeMap<String, String>>) {
-        if let Some(map) = map {
-            *FIELD_TYPE.write().unwrap() = map;
-        }
-    }
-
-    async fn gen_templates(&self, tables: Vec<Table2Comment>) -> Result<Vec<Table>> {
+    async fn gen_templates(
+        &self,
+        tables: Vec<Table2Comment>,
+        field_types: &FieldTypes,
+    ) -> Result<Vec<Table>> {
         let mut templates = vec![];
         let mut pool = self.pool.acquire().await?;
         for table in tables {
-            let fields = sqlx::query_as::<_, Field>(FIELD_SQL)
+            let mut fields = sqlx::query_as::<_, Field>(FIELD_SQL)
                 .bind(&table.table_name)
                 .fetch_all(&mut *pool)
                 .await?;
+            field_types.map_fields(&table.table_name, &mut fields)?;
             let mut struct_name = table.table_name.clone().to_camel_case();
             struct_name = Self::first_char_to_uppercase(&struct_name);
             let mut table = Table {
@@ -113,9 +122,21 @@ impl GenStruct for MysqlImpl {
                 comment: table.table_comment.unwrap_or_default(),
                 index_key: vec![],
                 unique_key: vec![],
+                primary_key: vec![],
+                foreign_keys: vec![],
+                soft_delete: None,
+                module_name: String::new(),
+                without_rowid: false,
+                rowid_alias: None,
+                custom_types: vec![],
+                engine: None,
+                time_precision: None,
+                kind: None,
             };
             let (index_key, unique_key) = self.index_key(&table.table_name).await?;
             table.index_key = index_key;
+            table.primary_key = self.primary_key(&table.table_name).await?;
+            table.foreign_keys = self.foreign_keys(&table.table_name).await?;
             table.unique_key = unique_key;
             templates.push(table);
         }
@@ -134,23 +155,9 @@ impl GenStruct for MysqlImpl {
             let is_unique = if_else!(i == 0, 1, 0);
             let field_name = String::from_utf8(index.get(4))?;
             if is_unique == 1 {
-                let v = unique_map.get(&key);
-                if v.is_none() {
-                    unique_map.insert(key, vec![field_name]);
-                } else {
-                    let mut v = v.unwrap().to_owned();
-                    v.push(field_name);
-                    unique_map.insert(key, v);
-                }
+                unique_map.entry(key).or_default().push(field_name);
             } else {
-                let v = map.get(&key);
-                if v.is_none() {
-                    map.insert(key, vec![field_name]);
-                } else {
-                    let mut v = v.unwrap().to_owned();
-                    v.push(field_name);
-                    map.insert(key, v);
-                }
+                map.entry(key).or_default().push(field_name);
             }
         }
         let mut list = vec![];
@@ -163,4 +170,32 @@ impl GenStruct for MysqlImpl {
         }
         Ok((list, unique_list))
     }
+
+    async fn primary_key(&self, table_name: &str) -> Result<Vec<String>> {
+        let rows = sqlx::query(PRIMARY_KEY_SQL)
+            .bind(table_name)
+            .fetch_all(&self.pool)
+            .await?;
+        let mut list = vec![];
+        for row in rows {
+            list.push(row.try_get("column_name")?);
+        }
+        Ok(list)
+    }
+
+    async fn foreign_keys(&self, table_name: &str) -> Result<Vec<ForeignKey>> {
+        let rows = sqlx::query(FOREIGN_KEY_SQL)
+            .bind(table_name)
+            .fetch_all(&self.pool)
+            .await?;
+        let mut list = vec![];
+        for row in rows {
+            list.push(ForeignKey {
+                column: row.try_get("column_name")?,
+                referenced_table: row.try_get("referenced_table")?,
+                referenced_column: row.try_get("referenced_column")?,
+            });
+        }
+        Ok(list)
+    }
 }
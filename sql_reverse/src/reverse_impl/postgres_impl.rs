@@ -1,15 +1,16 @@
 use crate::error::Result;
 use crate::reverse_impl::common::CustomConfig;
 use crate::reverse_impl::gen_struct::GenStruct;
-use crate::table::{Field, Table, Table2Comment};
+use crate::reverse_impl::type_map::FieldTypes;
+use crate::table::{Field, ForeignKey, Table, Table2Comment};
 use crate::template::kit::Kit;
 use inflector::Inflector;
 use regex::Regex;
 use sqlx::Row;
 use std::collections::BTreeMap;
-use std::sync::{LazyLock, RwLock};
+use std::sync::LazyLock;
 
-pub static FIELD_TYPE: LazyLock<RwLock<BTreeMap<String, String>>> = LazyLock::new(|| {
+pub static FIELD_TYPE: LazyLock<BTreeMap<String, String>> = LazyLock::new(|| {
     let mut map = BTreeMap::new();
     map.insert(r"^smallint$".to_string(), "i16".to_string());
     map.insert(r"^integer$".to_string(), "i32".to_string());
@@ -46,7 +47,7 @@ pub static FIELD_TYPE: LazyLock<RwLock<BTreeMap<String, String>>> = LazyLock::ne
     map.insert(r"^json$".to_string(), "String".to_string());
     map.insert(r"^jsonb$".to_string(), "String".to_string());
     map.insert(r"^jsonpath$".to_string(), "String".to_string());
-    RwLock::new(map)
+    map
 });
 
 #[derive(Debug)]
@@ -75,7 +76,7 @@ WHERE c.relkind = 'r'
 
 const TABLE_FIELDS: &str = "select a.attname                             as field_name,
        format_type(a.atttypid, a.atttypmod)  as field_type,
-       a.attnotnull                          as is_null,
+       cast(case when a.attnotnull then 0 else 1 end as bigint) as is_null,
        col_description(a.attrelid, a.attnum) as comment,
        pg_get_expr(d.adbin, d.adrelid)       as default_value
 from pg_class c,
@@ -90,10 +91,41 @@ from pg_class c,
                       and attrelid = c.oid) as d on a.attname = d.attname
 where c.relname = $2
   and a.attrelid = c.oid
-  and a.attnum > 0";
+  and a.attnum > 0
+  and not a.attisdropped
+order by a.attnum";
 
 const INDEX_SQL: &str = "SELECT indexdef FROM pg_indexes WHERE schemaname = $1 and tablename = $2";
+
+const PRIMARY_KEY_SQL: &str = "SELECT a.attname as column_name
+FROM pg_index i
+         JOIN pg_class c ON c.oid = i.indrelid
+         JOIN pg_namespace n ON n.oid = c.relnamespace
+         JOIN pg_attribute a ON a.attrelid = c.oid AND a.attnum = ANY (i.indkey)
+WHERE i.indisprimary
+  AND n.nspname = $1
+  AND c.relname = $2
+ORDER BY array_position(i.indkey::int2[], a.attnum)";
+
+const FOREIGN_KEY_SQL: &str = "SELECT a.attname  as column_name,
+       rc.relname as referenced_table,
+       ra.attname as referenced_column
+FROM pg_constraint con
+         JOIN pg_class c ON c.oid = con.conrelid
+         JOIN pg_namespace n ON n.oid = c.relnamespace
+         JOIN pg_class rc ON rc.oid = con.confrelid
+         CROSS JOIN LATERAL unnest(con.conkey, con.confkey) WITH ORDINALITY AS k(attnum, refnum, pos)
+         JOIN pg_attribute a ON a.attrelid = con.conrelid AND a.attnum = k.attnum
+         JOIN pg_attribute ra ON ra.attrelid = con.confrelid AND ra.attnum = k.refnum
+WHERE con.contype = 'f'
+  AND n.nspname = $1
+  AND c.relname = $2
+ORDER BY con.conname, k.pos";
 impl GenStruct for PostgresImpl {
+    fn config(&self) -> &CustomConfig {
+        &self.config
+    }
+
     async fn get_tables(&self) -> Result<Vec<Table2Comment>> {
         let mut pool = self.pool.acquire().await?;
         let mut tables = sqlx::query_as::<_, Table2Comment>(TABLES_SQL)
@@ -109,21 +141,20 @@ impl GenStruct for PostgresImpl {
         Ok(tables)
     }
 
-    async fn update_type_fields(&self, map: Option<BTreeMap<String, String>>) {
-        if let Some(map) = map {
-            *FIELD_TYPE.write().unwrap() = map;
-        }
-    }
-
-    async fn gen_templates(&self, tables: Vec<Table2Comment>) -> Result<Vec<Table>> {
+    async fn gen_templates(
+        &self,
+        tables: Vec<Table2Comment>,
+        field_types: &FieldTypes,
+    ) -> Result<Vec<Table>> {
         let mut templates = vec![];
         for table in tables {
             let mut pool = self.pool.acquire().await?;
-            let fields = sqlx::query_as::<_, Field>(TABLE_FIELDS)
+            let mut fields = sqlx::query_as::<_, Field>(TABLE_FIELDS)
                 .bind(&table.table_name)
                 .bind(&table.table_name)
                 .fetch_all(&mut *pool)
                 .await?;
+            field_types.map_fields(&table.table_name, &mut fields)?;
             let mut struct_name = table.table_name.clone().to_camel_case();
             struct_name = Self::first_char_to_uppercase(&struct_name);
             let mut table = Table {
@@ -133,10 +164,22 @@ impl GenStruct for PostgresImpl {
                 comment: table.table_comment.unwrap_or_default(),
                 index_key: vec![],
                 unique_key: vec![],
+                primary_key: vec![],
+                foreign_keys: vec![],
+                soft_delete: None,
+                module_name: String::new(),
+                without_rowid: false,
+                rowid_alias: None,
+                custom_types: vec![],
+                engine: None,
+                time_precision: None,
+                kind: None,
             };
 
             let (index_key, unique_key) = self.index_key(&table.table_name).await?;
             table.index_key = index_key;
+            table.primary_key = self.primary_key(&table.table_name).await?;
+            table.foreign_keys = self.foreign_keys(&table.table_name).await?;
             table.unique_key = unique_key;
             templates.push(table);
         }
@@ -163,7 +206,8 @@ impl GenStruct for PostgresImpl {
                 let v: Vec<&str> = v.split(", ").collect();
                 let mut index = vec![];
                 for v in v {
-                    index.push(v.to_owned());
+                    // 大写、空格等字段名在 indexdef 中带双引号
+                    index.push(v.trim_matches('"').replace("\"\"", "\""));
                 }
                 if value.contains("UNIQUE") || value.contains("unique") {
                     unique_list.push(index)
@@ -174,4 +218,33 @@ impl GenStruct for PostgresImpl {
         }
         Ok((index_list, unique_list))
     }
+    async fn primary_key(&self, table_name: &str) -> Result<Vec<String>> {
+        let rows = sqlx::query(PRIMARY_KEY_SQL)
+            .bind(self.config.schemaname.to_owned().unwrap_or_default())
+            .bind(table_name)
+            .fetch_all(&self.pool)
+            .await?;
+        let mut list = vec![];
+        for row in rows {
+            list.push(row.try_get("column_name")?);
+        }
+        Ok(list)
+    }
+
+    async fn foreign_keys(&self, table_name: &str) -> Result<Vec<ForeignKey>> {
+        let rows = sqlx::query(FOREIGN_KEY_SQL)
+            .bind(self.config.schemaname.to_owned().unwrap_or_default())
+            .bind(table_name)
+            .fetch_all(&self.pool)
+            .await?;
+        let mut list = vec![];
+        for row in rows {
+            list.push(ForeignKey {
+                column: row.try_get("column_name")?,
+                referenced_table: row.try_get("referenced_table")?,
+                referenced_column: row.try_get("referenced_column")?,
+            });
+        }
+        Ok(list)
+    }
 }
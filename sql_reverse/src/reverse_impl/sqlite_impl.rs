@@ -1,18 +1,17 @@
 use crate::error::Result;
 use crate::reverse_impl::common::CustomConfig;
 use crate::reverse_impl::gen_struct::GenStruct;
-use crate::table::sqlite::Fields;
-use crate::table::{Table, Table2Comment};
+use crate::reverse_impl::type_map::FieldTypes;
+use crate::table::{Field, ForeignKey, Table, Table2Comment};
 use crate::template::kit::Kit;
 use fn_macro::btreemap;
 use inflector::Inflector;
-use regex::Regex;
 use sqlx::Row;
 use std::collections::BTreeMap;
-use std::sync::{LazyLock, RwLock};
+use std::sync::LazyLock;
 
-pub static FIELD_TYPE: LazyLock<RwLock<BTreeMap<String, String>>> = LazyLock::new(|| {
-    RwLock::new(btreemap!(
+pub static FIELD_TYPE: LazyLock<BTreeMap<String, String>> = LazyLock::new(|| {
+    btreemap!(
         r"^INTEGER$".to_string() => "i64".to_string(),
         r"^integer$".to_string() => "i64".to_string(),
         r"^TEXT$".to_string() => "String".to_string(),
@@ -24,9 +23,82 @@ pub static FIELD_TYPE: LazyLock<RwLock<BTreeMap<String, String>>> = LazyLock::ne
         r"^BOOLEAN$".to_string() => "bool".to_string(),
         r"^VARCHAR".to_string() => "String".to_string(),
         r"^TIMESTAMP".to_string() => "chrono::NaiveDateTime".to_string(),
+        r"(?i)^bool(ean)?$".to_string() => "bool".to_string(),
+        r"(?i)^datetime".to_string() => "chrono::NaiveDateTime".to_string(),
+        r"(?i)^date$".to_string() => "chrono::NaiveDate".to_string(),
 
-    ))
+    )
 });
+
+/// SQLite 类型亲和性, 按声明类型判断, 规则见 https://www.sqlite.org/datatype3.html#determination_of_column_affinity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Affinity {
+    Integer,
+    Text,
+    Blob,
+    Real,
+    Numeric,
+}
+
+impl Affinity {
+    pub fn of(declared_type: &str) -> Self {
+        let declared_type = declared_type.to_uppercase();
+        if declared_type.contains("INT") {
+            Affinity::Integer
+        } else if ["CHAR", "CLOB", "TEXT"]
+            .iter()
+            .any(|v| declared_type.contains(v))
+        {
+            Affinity::Text
+        } else if declared_type.contains("BLOB") || declared_type.trim().is_empty() {
+            Affinity::Blob
+        } else if ["REAL", "FLOA", "DOUB"]
+            .iter()
+            .any(|v| declared_type.contains(v))
+        {
+            Affinity::Real
+        } else {
+            Affinity::Numeric
+        }
+    }
+
+    pub fn rust_type(&self) -> &'static str {
+        match self {
+            Affinity::Integer => "i64",
+            Affinity::Text => "String",
+            Affinity::Blob => "Vec<u8>",
+            Affinity::Real | Affinity::Numeric => "f64",
+        }
+    }
+}
+
+/// 字段类型: STRICT 表的 INT/INTEGER/REAL/TEXT/BLOB 按存储类型精确映射,
+/// 其他类型先查类型映射, 未匹配时按类型亲和性映射, STRICT 表的 ANY 未匹配时使用 String
+pub fn field_type(
+    field_types: &FieldTypes,
+    database_field_type: &str,
+    column: &str,
+    strict: bool,
+) -> String {
+    if strict {
+        match database_field_type.to_uppercase().as_str() {
+            "INT" | "INTEGER" => return "i64".to_string(),
+            "REAL" => return "f64".to_string(),
+            "TEXT" => return "String".to_string(),
+            "BLOB" => return "Vec<u8>".to_string(),
+            _ => {}
+        }
+    }
+    if let Ok(Some(v)) = field_types.match_field_type(database_field_type) {
+        return v;
+    }
+    if strict {
+        return field_types
+            .field_type(database_field_type, column)
+            .unwrap_or_default();
+    }
+    Affinity::of(database_field_type).rust_type().to_string()
+}
 pub struct SqliteImpl {
     pub config: CustomConfig,
     pub pool: sqlx::SqlitePool,
@@ -37,13 +109,52 @@ impl SqliteImpl {
         let pool = sqlx::SqlitePool::connect(&config.db_url).await?;
         Ok(SqliteImpl { config, pool })
     }
+
+    /// 是否为 WITHOUT ROWID 表, 是否为 STRICT 表
+    async fn table_options(&self, table_name: &str) -> Result<(bool, bool)> {
+        let row = sqlx::query(WITHOUT_ROWID_SQL)
+            .bind(table_name)
+            .fetch_one(&self.pool)
+            .await?;
+        let without_rowid: i64 = row.try_get("without_rowid")?;
+        let strict: i64 = row.try_get("strict")?;
+        Ok((without_rowid == 1, strict == 1))
+    }
+
+    /// rowid 的别名字段: 有 rowid 的表中单字段且声明类型为 INTEGER 的主键
+    async fn rowid_alias(&self, table_name: &str) -> Result<Option<String>> {
+        let rows = sqlx::query(PRIMARY_KEY_SQL)
+            .bind(table_name)
+            .fetch_all(&self.pool)
+            .await?;
+        if rows.len() != 1 {
+            return Ok(None);
+        }
+        let field_type: String = rows[0].try_get("field_type")?;
+        if !field_type.eq_ignore_ascii_case("INTEGER") {
+            return Ok(None);
+        }
+        Ok(Some(rows[0].try_get("column_name")?))
+    }
 }
 
-const TABLES_SQL: &str = "select name as table_name from sqlite_master where type='table'";
-const FIELD_SQL: &str = "select sql from sqlite_master where type='table' and name = ?";
+const TABLES_SQL: &str = r"select name as table_name from sqlite_master where type='table' and name not like 'sqlite\_%' escape '\' order by name";
+/// hidden: 0 普通列, 1 虚拟表隐藏列(跳过), 2/3 生成列
+const FIELD_SQL: &str = r#"select x.name as field_name, x.type as field_type, x."notnull" as not_null, x.dflt_value as default_value from pragma_table_xinfo(?1) as x, pragma_table_list(?1) as t where t.schema = 'main' and x.hidden != 1 order by x.cid"#;
+const WITHOUT_ROWID_SQL: &str =
+    "select wr as without_rowid, strict from pragma_table_list(?) where schema = 'main'";
 
-const INDEX_SQL: &str = "select sql from sqlite_master where type='index' and name = ?";
+/// origin: c CREATE INDEX, u UNIQUE 约束, pk 主键(主键单独处理)
+const INDEX_SQL: &str = r#"select name, "unique" as is_unique from pragma_index_list(?) where origin != 'pk' order by name"#;
+/// 表达式索引的列名为 null
+const INDEX_INFO_SQL: &str = "select name from pragma_index_info(?) order by seqno";
+const PRIMARY_KEY_SQL: &str = "select name as column_name, type as field_type from pragma_table_info(?) where pk > 0 order by pk";
+const FOREIGN_KEY_SQL: &str = r#"select "from" as column_name, "table" as referenced_table, coalesce("to", '') as referenced_column from pragma_foreign_key_list(?) order by id, seq"#;
 impl GenStruct for SqliteImpl {
+    fn config(&self) -> &CustomConfig {
+        &self.config
+    }
+
     async fn get_tables(&self) -> Result<Vec<Table2Comment>> {
         let mut pool = self.pool.acquire().await?;
         let mut tables = sqlx::query_as::<_, Table2Comment>(TABLES_SQL)
@@ -58,35 +169,63 @@ impl GenStruct for SqliteImpl {
         Ok(tables)
     }
 
-    async fn update_type_fields(&self, map: Option<BTreeMap<String, String>>) {
-        if let Some(map) = map {
-            *FIELD_TYPE.write().unwrap() = map;
-        }
-    }
-
-    async fn gen_templates(&self, tables: Vec<Table2Comment>) -> Result<Vec<Table>> {
+    async fn gen_templates(
+        &self,
+        tables: Vec<Table2Comment>,
+        field_types: &FieldTypes,
+    ) -> Result<Vec<Table>> {
         let mut templates = vec![];
         let mut pool = self.pool.acquire().await?;
         for table in tables {
-            let fields = sqlx::query_as::<_, Fields>(FIELD_SQL)
+            let mut fields = sqlx::query_as::<_, Field>(FIELD_SQL)
                 .bind(&table.table_name)
-                .fetch_one(&mut *pool)
+                .fetch_all(&mut *pool)
                 .await?;
+            let (without_rowid, strict) = self.table_options(&table.table_name).await?;
+            for field in fields.iter_mut() {
+                let column = format!("{}.{}", table.table_name, field.column_name);
+                field.field_type =
+                    field_type(field_types, &field.database_field_type, &column, strict);
+            }
             let mut struct_name = table.table_name.clone().to_camel_case();
             struct_name = Self::first_char_to_uppercase(&struct_name);
             let mut table = Table {
                 table_name: table.table_name.to_owned(),
                 struct_name,
-                fields: fields.fields,
+                fields,
                 comment: table.table_comment.unwrap_or_default(),
                 index_key: vec![],
                 unique_key: vec![],
+                primary_key: vec![],
+                foreign_keys: vec![],
+                soft_delete: None,
+                module_name: String::new(),
+                without_rowid: false,
+                rowid_alias: None,
+                custom_types: vec![],
+                engine: None,
+                time_precision: None,
+                kind: None,
             };
-            if !fields.keys.is_empty() {
-                table.unique_key.push(fields.keys);
-            }
             let (index_key, unique_key) = self.index_key(&table.table_name).await?;
             table.index_key = index_key;
+            table.primary_key = self.primary_key(&table.table_name).await?;
+            if !table.primary_key.is_empty() {
+                table.unique_key.push(table.primary_key.clone());
+            }
+            table.without_rowid = without_rowid;
+            if !without_rowid {
+                table.rowid_alias = self.rowid_alias(&table.table_name).await?;
+            }
+            // WITHOUT ROWID 表的主键和 rowid 别名字段不会为 null
+            for field in table.fields.iter_mut() {
+                if (without_rowid && table.primary_key.contains(&field.column_name))
+                    || table.rowid_alias.as_ref() == Some(&field.column_name)
+                {
+                    field.is_null = 0;
+                }
+            }
+            table.foreign_keys = self.foreign_keys(&table.table_name).await?;
             table.unique_key.extend(unique_key);
             templates.push(table);
         }
@@ -100,21 +239,152 @@ impl GenStruct for SqliteImpl {
             .await?;
         let mut index_list = vec![];
         let mut unique_list = vec![];
-        let re = Regex::new("\\(.*\\)")?;
-        for row in rows {
-            let sql: &str = row.get(0);
-            if re.is_match(sql) {
-                if let Some(data) = re.find(sql) {
-                    let index: Vec<String> =
-                        data.as_str().split(",").map(|a| a.to_string()).collect();
-                    if sql.contains("unique") {
-                        unique_list.push(index);
-                    } else {
-                        index_list.push(index);
-                    }
+        'index: for row in rows {
+            let name: String = row.try_get("name")?;
+            let is_unique: i64 = row.try_get("is_unique")?;
+            let columns = sqlx::query(INDEX_INFO_SQL)
+                .bind(&name)
+                .fetch_all(&self.pool)
+                .await?;
+            let mut index = vec![];
+            for column in columns {
+                match column.try_get::<Option<String>, _>("name")? {
+                    Some(column) => index.push(column),
+                    // 表达式索引无法生成按字段查询的方法, 跳过
+                    None => continue 'index,
                 }
             }
+            if is_unique == 1 {
+                unique_list.push(index);
+            } else {
+                index_list.push(index);
+            }
         }
         Ok((index_list, unique_list))
     }
+    async fn primary_key(&self, table_name: &str) -> Result<Vec<String>> {
+        let rows = sqlx::query(PRIMARY_KEY_SQL)
+            .bind(table_name)
+            .fetch_all(&self.pool)
+            .await?;
+        let mut list = vec![];
+        for row in rows {
+            list.push(row.try_get("column_name")?);
+        }
+        Ok(list)
+    }
+
+    async fn foreign_keys(&self, table_name: &str) -> Result<Vec<ForeignKey>> {
+        let rows = sqlx::query(FOREIGN_KEY_SQL)
+            .bind(table_name)
+            .fetch_all(&self.pool)
+            .await?;
+        let mut list = vec![];
+        for row in rows {
+            list.push(ForeignKey {
+                column: row.try_get("column_name")?,
+                referenced_table: row.try_get("referenced_table")?,
+                referenced_column: row.try_get("referenced_column")?,
+            });
+        }
+        Ok(list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field_types() -> FieldTypes {
+        FieldTypes::new(FIELD_TYPE.clone().into())
+    }
+
+    #[tokio::test]
+    async fn test_gen_templates() {
+        let config = CustomConfig {
+            db_url: "sqlite::memory:".to_string(),
+            ..Default::default()
+        };
+        let sqlite = SqliteImpl::init(config).await.unwrap();
+        for sql in [
+            "create table authors (id integer primary key autoincrement, name text not null)",
+            r#"create table "book items" (
+                id INTEGER PRIMARY KEY,
+                "full title" varchar(255) not null default 'untitled',
+                price decimal(10,2) check (price > 0),
+                author_id integer,
+                isbn text unique,
+                FOREIGN KEY (author_id) REFERENCES authors(id)
+            )"#,
+            r#"create index idx_price on "book items"(price, author_id)"#,
+            r#"create index idx_lower on "book items"(lower(isbn))"#,
+            "create table tags (book_id integer, tag text, primary key (book_id, tag))",
+            "insert into authors (name) values ('a')",
+        ] {
+            sqlx::query(sql).execute(&sqlite.pool).await.unwrap();
+        }
+        let tables = sqlite.get_tables().await.unwrap();
+        let names: Vec<&str> = tables.iter().map(|v| v.table_name.as_str()).collect();
+        assert_eq!(names, ["authors", "book items", "tags"]);
+
+        let templates = sqlite.gen_templates(tables, &field_types()).await.unwrap();
+        let book = &templates[1];
+        let columns: Vec<&str> = book.fields.iter().map(|v| v.column_name.as_str()).collect();
+        assert_eq!(columns, ["id", "full title", "price", "author_id", "isbn"]);
+        assert_eq!(book.fields[0].is_null, 0);
+        assert_eq!(book.fields[1].is_null, 0);
+        assert_eq!(book.fields[1].default.as_deref(), Some("'untitled'"));
+        assert_eq!(book.fields[2].database_field_type, "decimal(10,2)");
+        assert_eq!(book.primary_key, ["id"]);
+        assert_eq!(book.unique_key, [vec!["id"], vec!["isbn"]]);
+        assert_eq!(book.index_key, [vec!["price", "author_id"]]);
+        assert_eq!(book.foreign_keys[0].referenced_table, "authors");
+
+        let tags = &templates[2];
+        assert_eq!(tags.primary_key, ["book_id", "tag"]);
+        assert_eq!(tags.unique_key, [vec!["book_id", "tag"]]);
+        assert_eq!(tags.fields[0].is_null, 1);
+    }
+
+    #[test]
+    fn test_affinity() {
+        assert_eq!(Affinity::of("UNSIGNED BIG INT"), Affinity::Integer);
+        assert_eq!(Affinity::of("VARCHAR(255)"), Affinity::Text);
+        assert_eq!(Affinity::of("nchar(5)"), Affinity::Text);
+        assert_eq!(Affinity::of(""), Affinity::Blob);
+        assert_eq!(Affinity::of("DOUBLE PRECISION"), Affinity::Real);
+        assert_eq!(Affinity::of("DECIMAL(10,2)"), Affinity::Numeric);
+        // POINT 包含 INT, 按规则优先判断为 INTEGER
+        assert_eq!(Affinity::of("FLOATING POINT"), Affinity::Integer);
+    }
+
+    #[tokio::test]
+    async fn test_strict_without_rowid() {
+        let config = CustomConfig {
+            db_url: "sqlite::memory:".to_string(),
+            ..Default::default()
+        };
+        let sqlite = SqliteImpl::init(config).await.unwrap();
+        for sql in [
+            "create table kv (k text primary key, n int) strict, without rowid",
+            "create table s (id integer primary key, n int, big unsigned big int, at datetime)",
+        ] {
+            sqlx::query(sql).execute(&sqlite.pool).await.unwrap();
+        }
+        let tables = sqlite.get_tables().await.unwrap();
+        let templates = sqlite.gen_templates(tables, &field_types()).await.unwrap();
+        let kv = &templates[0];
+        assert!(kv.without_rowid);
+        assert_eq!(kv.rowid_alias, None);
+        assert_eq!(kv.fields[0].is_null, 0);
+        // STRICT 表的 INT 是 64 位整数
+        assert_eq!(kv.fields[1].field_type, "i64");
+
+        let s = &templates[1];
+        assert!(!s.without_rowid);
+        assert_eq!(s.rowid_alias.as_deref(), Some("id"));
+        assert_eq!(s.fields[1].field_type, "i32");
+        assert_eq!(s.fields[2].field_type, "i64");
+        assert_eq!(s.fields[3].field_type, "chrono::NaiveDateTime");
+    }
 }
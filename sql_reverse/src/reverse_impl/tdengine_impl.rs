@@ -1,19 +1,21 @@
 use crate::error::Result;
-use crate::keywords::LANGUAGE;
 use crate::reverse_impl::common::CustomConfig;
 use crate::reverse_impl::gen_struct::GenStruct;
-use crate::table::tdengine::{TdengineDescribeRow, TdengineNormalTable, TdengineStable};
-use crate::table::{Field, Table, Table2Comment};
+use crate::reverse_impl::type_map::FieldTypes;
+use crate::table::tdengine::{
+    TdengineDatabase, TdengineDescribeRow, TdengineNormalTable, TdengineStable,
+};
+use crate::table::{Field, Table, Table2Comment, TableKind};
 use crate::template::kit::Kit;
 use fn_macro::btreemap;
 use futures::TryStreamExt;
 use inflector::Inflector;
-use std::collections::BTreeMap;
-use std::sync::{LazyLock, RwLock};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::LazyLock;
 use taos::{AsyncFetchable, AsyncQueryable, AsyncTBuilder};
 
-pub static FIELD_TYPE: LazyLock<RwLock<BTreeMap<String, String>>> = LazyLock::new(|| {
-    let map = btreemap!(
+pub static FIELD_TYPE: LazyLock<BTreeMap<String, String>> = LazyLock::new(|| {
+    btreemap!(
         // Timestamp
         "^TIMESTAMP$".to_string() => "chrono::NaiveDateTime".to_string(),
         // Boolean
@@ -43,14 +45,13 @@ pub static FIELD_TYPE: LazyLock<RwLock<BTreeMap<String, String>>> = LazyLock::ne
         "^JSON$".to_string() => "serde_json::Value".to_string(),
         // Decimal type
         "^DECIMAL.*$".to_string() => "String".to_string()
-    );
-    RwLock::new(map)
+    )
 });
 
 #[derive(Clone)]
 pub struct TdengineImpl {
     pub config: CustomConfig,
-    pub builder: taos::TaosBuilder,
+    pub pool: taos::TaosPool,
 }
 
 impl std::fmt::Debug for TdengineImpl {
@@ -65,58 +66,90 @@ impl Kit for TdengineImpl {}
 
 impl TdengineImpl {
     pub async fn init(config: CustomConfig) -> Result<Self> {
+        // WebSocket 协议随 tdengine 启用, 原生协议需要 taos-native
+        let (scheme, feature) = Self::protocol_feature(&config.db_url);
+        if feature == "taos-native" && !cfg!(feature = "taos-native") {
+            return Err(crate::error::Error::TaosFeature(
+                scheme.to_string(),
+                feature,
+            ));
+        }
         let builder = taos::TaosBuilder::from_dsn(&config.db_url)?;
-        Ok(Self { config, builder })
+        let pool = AsyncTBuilder::pool(builder)?;
+        Ok(Self { config, pool })
     }
 
-    async fn connect(&self) -> Result<taos::Taos> {
-        Ok(self.builder.build().await?)
+    /// DSN 协议及其所需的 cargo feature, taos:// 为原生协议, taos+ws:// 与 ws:// 为 WebSocket(tdengine)
+    fn protocol_feature(dsn: &str) -> (&str, &'static str) {
+        let scheme = dsn
+            .split_once("://")
+            .map(|(scheme, _)| scheme)
+            .unwrap_or(dsn);
+        match scheme {
+            "taos" | "tmq" => (scheme, "taos-native"),
+            _ => (scheme, "tdengine"),
+        }
     }
 
-    fn convert_field(row: &TdengineDescribeRow) -> Field {
-        let field_type = Self::get_field_type(
-            &row.field_type,
-            &row.field,
-            &FIELD_TYPE.read().unwrap(),
-        )
-        .unwrap_or_default();
+    /// 从连接池取出连接, 各查询复用同一批连接
+    async fn connect(&self) -> Result<impl std::ops::Deref<Target = taos::Taos>> {
+        self.pool
+            .get()
+            .await
+            .map_err(|e| crate::error::Error::Taos(taos::Error::from_any(e)))
+    }
+
+    fn convert_field(
+        table_name: &str,
+        row: &TdengineDescribeRow,
+        field_types: &FieldTypes,
+    ) -> Field {
+        let field_type = field_types
+            .field_type(&row.field_type, &format!("{}.{}", table_name, row.field))
+            .unwrap_or_default();
         let field_name_camel_case = row.field.clone().to_camel_case();
         let first_char_uppercase = Self::first_char_to_uppercase(&field_name_camel_case);
-        // In TDengine, TIMESTAMP (first column) is NOT NULL; all other columns are nullable
-        let is_null = if row.field_type == "TIMESTAMP" { 0 } else { 1 };
-        let comment = if row.note == "TAG" {
-            "[TAG]".to_string()
+        let is_tag = row.note == "TAG";
+        // In TDengine, TIMESTAMP (first column) is NOT NULL; all other columns and tags are nullable
+        let is_null = if row.field_type == "TIMESTAMP" && !is_tag {
+            0
         } else {
-            String::new()
+            1
         };
         Field {
-            field_name: LANGUAGE.check_field_name(&row.field),
+            column_name: row.field.to_owned(),
+            field_name: row.field.to_owned(),
             FieldName: first_char_uppercase,
-            fieldName: LANGUAGE.check_field_name(&field_name_camel_case),
+            fieldName: field_name_camel_case,
             database_field_type: row.field_type.clone(),
             field_type,
-            comment,
-            is_null: is_null,
+            comment: String::new(),
+            is_null,
             default: None,
+            rename: None,
+            default_kind: None,
+            generated: false,
+            is_tag,
         }
     }
 
-    /// Build tag columns as unique_key (similar to ClickHouse primary key mapping)
-    fn build_tag_keys(rows: &[TdengineDescribeRow]) -> Vec<Vec<String>> {
-        let tag_fields: Vec<String> = rows
-            .iter()
-            .filter(|r| r.note == "TAG")
-            .map(|r| r.field.clone())
-            .collect();
-        if tag_fields.is_empty() {
-            vec![]
+    /// 超级表和子表的 DESCRIBE 结果都包含标签列, 按是否在 ins_stables 中区分
+    fn table_kind(rows: &[TdengineDescribeRow], is_stable: bool) -> TableKind {
+        if is_stable {
+            TableKind::Supertable
+        } else if rows.iter().any(|r| r.note == "TAG") {
+            TableKind::Child
         } else {
-            vec![tag_fields]
+            TableKind::Normal
         }
     }
 }
 
 impl GenStruct for TdengineImpl {
+    fn config(&self) -> &CustomConfig {
+        &self.config
+    }
+
     async fn get_tables(&self) -> Result<Vec<Table2Comment>> {
         let database = self.config.schemaname.clone().unwrap_or_default();
         let taos = self.connect().await?;
@@ -149,6 +182,29 @@ impl GenStruct for TdengineImpl {
             .await
             .map_err(crate::error::Error::Taos)?;
 
+        // Child tables can number in the millions, so they are only read when listed in include_tables
+        let include_tables = self.config.include_tables.clone().unwrap_or_default();
+        let child_tables: Vec<TdengineNormalTable> = if include_tables.is_empty() {
+            vec![]
+        } else {
+            let names: Vec<String> = include_tables
+                .iter()
+                .map(|v| format!("'{}'", v.replace('\\', "\\\\").replace('\'', "\\'")))
+                .collect();
+            let child_sql = format!(
+                "SELECT table_name, table_comment FROM information_schema.ins_tables WHERE db_name = '{}' AND type = 'CHILD_TABLE' AND table_name IN ({})",
+                database,
+                names.join(", ")
+            );
+            taos.query(&child_sql)
+                .await
+                .map_err(crate::error::Error::Taos)?
+                .deserialize()
+                .try_collect()
+                .await
+                .map_err(crate::error::Error::Taos)?
+        };
+
         let mut tables: Vec<Table2Comment> = Vec::new();
 
         // Add supertables
@@ -159,8 +215,8 @@ impl GenStruct for TdengineImpl {
             });
         }
 
-        // Add normal tables
-        for nt in normal_tables {
+        // Add normal tables and child tables
+        for nt in normal_tables.into_iter().chain(child_tables) {
             tables.push(Table2Comment {
                 table_name: nt.table_name,
                 table_comment: nt.table_comment,
@@ -176,15 +232,40 @@ impl GenStruct for TdengineImpl {
         Ok(tables)
     }
 
-    async fn update_type_fields(&self, map: Option<BTreeMap<String, String>>) {
-        if let Some(map) = map {
-            *FIELD_TYPE.write().unwrap() = map;
-        }
-    }
-
-    async fn gen_templates(&self, tables: Vec<Table2Comment>) -> Result<Vec<Table>> {
+    async fn gen_templates(
+        &self,
+        tables: Vec<Table2Comment>,
+        field_types: &FieldTypes,
+    ) -> Result<Vec<Table>> {
         let database = self.config.schemaname.clone().unwrap_or_default();
         let taos = self.connect().await?;
+        // 生成代码按数据库精度把 NaiveDateTime 转成时间戳绑定
+        let precision_sql = format!(
+            "SELECT `precision` FROM information_schema.ins_databases WHERE name = '{}'",
+            database
+        );
+        let databases: Vec<TdengineDatabase> = taos
+            .query(&precision_sql)
+            .await
+            .map_err(crate::error::Error::Taos)?
+            .deserialize()
+            .try_collect()
+            .await
+            .map_err(crate::error::Error::Taos)?;
+        let time_precision = databases.into_iter().next().map(|v| v.precision);
+        let stables_sql = format!(
+            "SELECT stable_name, table_comment FROM information_schema.ins_stables WHERE db_name = '{}'",
+            database
+        );
+        let stables: Vec<TdengineStable> = taos
+            .query(&stables_sql)
+            .await
+            .map_err(crate::error::Error::Taos)?
+            .deserialize()
+            .try_collect()
+            .await
+            .map_err(crate::error::Error::Taos)?;
+        let stables: HashSet<String> = stables.into_iter().map(|v| v.stable_name).collect();
         let mut templates = vec![];
         for table in tables {
             let describe_sql = format!("DESCRIBE `{}`.`{}`", database, table.table_name);
@@ -197,10 +278,15 @@ impl GenStruct for TdengineImpl {
                 .await
                 .map_err(crate::error::Error::Taos)?;
 
-            let fields: Vec<Field> = rows.iter().map(|r| Self::convert_field(r)).collect();
+            let fields: Vec<Field> = rows
+                .iter()
+                .map(|v| Self::convert_field(&table.table_name, v, field_types))
+                .collect();
             let mut struct_name = table.table_name.clone().to_camel_case();
             struct_name = Self::first_char_to_uppercase(&struct_name);
-            let unique_key = Self::build_tag_keys(&rows);
+            let kind = Self::table_kind(&rows, stables.contains(&table.table_name));
+            // The first column is always the TIMESTAMP primary key
+            let primary_key: Vec<String> = rows.iter().take(1).map(|r| r.field.clone()).collect();
 
             let table = Table {
                 table_name: table.table_name.to_owned(),
@@ -208,7 +294,17 @@ impl GenStruct for TdengineImpl {
                 fields,
                 comment: table.table_comment.unwrap_or_default(),
                 index_key: vec![],
-                unique_key,
+                unique_key: vec![],
+                primary_key,
+                foreign_keys: vec![],
+                soft_delete: None,
+                module_name: String::new(),
+                without_rowid: false,
+                rowid_alias: None,
+                custom_types: vec![],
+                engine: None,
+                time_precision: time_precision.clone(),
+                kind: Some(kind),
             };
             templates.push(table);
         }
@@ -228,27 +324,28 @@ mod tests {
 
     // ========== FIELD_TYPE mapping tests ==========
 
+    fn field_types() -> FieldTypes {
+        FieldTypes::new(FIELD_TYPE.clone().into())
+    }
+
     #[test]
     fn test_field_type_timestamp() {
-        let map = FIELD_TYPE.read().unwrap();
+        let map = field_types();
         assert_eq!(
-            TdengineImpl::get_field_type("TIMESTAMP", "ts", &map).unwrap(),
+            map.field_type("TIMESTAMP", "ts").unwrap(),
             "chrono::NaiveDateTime"
         );
     }
 
     #[test]
     fn test_field_type_bool() {
-        let map = FIELD_TYPE.read().unwrap();
-        assert_eq!(
-            TdengineImpl::get_field_type("BOOL", "f", &map).unwrap(),
-            "bool"
-        );
+        let map = field_types();
+        assert_eq!(map.field_type("BOOL", "f").unwrap(), "bool");
     }
 
     #[test]
     fn test_field_type_signed_integers() {
-        let map = FIELD_TYPE.read().unwrap();
+        let map = field_types();
         let cases = vec![
             ("TINYINT", "i8"),
             ("SMALLINT", "i16"),
@@ -256,15 +353,14 @@ mod tests {
             ("BIGINT", "i64"),
         ];
         for (td_type, expected_rust) in cases {
-            let result =
-                TdengineImpl::get_field_type(td_type, "test_field", &map).unwrap();
+            let result = map.field_type(td_type, "test_field").unwrap();
             assert_eq!(result, expected_rust, "type mapping failed for {}", td_type);
         }
     }
 
     #[test]
     fn test_field_type_unsigned_integers() {
-        let map = FIELD_TYPE.read().unwrap();
+        let map = field_types();
         let cases = vec![
             ("TINYINT UNSIGNED", "u8"),
             ("SMALLINT UNSIGNED", "u16"),
@@ -272,75 +368,42 @@ mod tests {
             ("BIGINT UNSIGNED", "u64"),
         ];
         for (td_type, expected_rust) in cases {
-            let result =
-                TdengineImpl::get_field_type(td_type, "test_field", &map).unwrap();
+            let result = map.field_type(td_type, "test_field").unwrap();
             assert_eq!(result, expected_rust, "type mapping failed for {}", td_type);
         }
     }
 
     #[test]
     fn test_field_type_floats() {
-        let map = FIELD_TYPE.read().unwrap();
-        assert_eq!(
-            TdengineImpl::get_field_type("FLOAT", "f", &map).unwrap(),
-            "f32"
-        );
-        assert_eq!(
-            TdengineImpl::get_field_type("DOUBLE", "f", &map).unwrap(),
-            "f64"
-        );
+        let map = field_types();
+        assert_eq!(map.field_type("FLOAT", "f").unwrap(), "f32");
+        assert_eq!(map.field_type("DOUBLE", "f").unwrap(), "f64");
     }
 
     #[test]
     fn test_field_type_string_types() {
-        let map = FIELD_TYPE.read().unwrap();
-        assert_eq!(
-            TdengineImpl::get_field_type("BINARY", "f", &map).unwrap(),
-            "String"
-        );
-        assert_eq!(
-            TdengineImpl::get_field_type("VARCHAR", "f", &map).unwrap(),
-            "String"
-        );
-        assert_eq!(
-            TdengineImpl::get_field_type("NCHAR", "f", &map).unwrap(),
-            "String"
-        );
+        let map = field_types();
+        assert_eq!(map.field_type("BINARY", "f").unwrap(), "String");
+        assert_eq!(map.field_type("VARCHAR", "f").unwrap(), "String");
+        assert_eq!(map.field_type("NCHAR", "f").unwrap(), "String");
     }
 
     #[test]
     fn test_field_type_json() {
-        let map = FIELD_TYPE.read().unwrap();
-        assert_eq!(
-            TdengineImpl::get_field_type("JSON", "f", &map).unwrap(),
-            "serde_json::Value"
-        );
+        let map = field_types();
+        assert_eq!(map.field_type("JSON", "f").unwrap(), "serde_json::Value");
     }
 
     #[test]
     fn test_field_type_binary_data() {
-        let map = FIELD_TYPE.read().unwrap();
-        assert_eq!(
-            TdengineImpl::get_field_type("VARBINARY", "f", &map).unwrap(),
-            "String"
-        );
-        assert_eq!(
-            TdengineImpl::get_field_type("BLOB", "f", &map).unwrap(),
-            "String"
-        );
-        assert_eq!(
-            TdengineImpl::get_field_type("GEOMETRY", "f", &map).unwrap(),
-            "String"
-        );
+        let map = field_types();
+        assert_eq!(map.field_type("VARBINARY", "f").unwrap(), "String");
+        assert_eq!(map.field_type("BLOB", "f").unwrap(), "String");
+        assert_eq!(map.field_type("GEOMETRY", "f").unwrap(), "String");
     }
 
     // ========== convert_field tests ==========
 
-    /// Initialize the LANGUAGE global for tests
-    async fn init_language() {
-        let _ = crate::keywords::get_or_init("rs").await;
-    }
-
     fn make_describe_row(field: &str, field_type: &str, note: &str) -> TdengineDescribeRow {
         TdengineDescribeRow {
             field: field.to_string(),
@@ -350,80 +413,104 @@ mod tests {
         }
     }
 
-    #[tokio::test]
-    async fn test_convert_field_timestamp() {
-        init_language().await;
+    #[test]
+    fn test_convert_field_timestamp() {
         let row = make_describe_row("ts", "TIMESTAMP", "");
-        let field = TdengineImpl::convert_field(&row);
+        let field = TdengineImpl::convert_field("t", &row, &field_types());
         assert_eq!(field.field_name, "ts");
         assert_eq!(field.field_type, "chrono::NaiveDateTime");
         assert_eq!(field.is_null, 0); // TIMESTAMP is NOT NULL
         assert_eq!(field.comment, "");
     }
 
-    #[tokio::test]
-    async fn test_convert_field_regular_column() {
-        init_language().await;
+    #[test]
+    fn test_convert_field_regular_column() {
         let row = make_describe_row("current", "FLOAT", "");
-        let field = TdengineImpl::convert_field(&row);
+        let field = TdengineImpl::convert_field("t", &row, &field_types());
         assert_eq!(field.field_name, "current");
         assert_eq!(field.field_type, "f32");
         assert_eq!(field.is_null, 1); // Regular columns are nullable
         assert_eq!(field.comment, "");
+        assert!(!field.is_tag);
     }
 
-    #[tokio::test]
-    async fn test_convert_field_tag_column() {
-        init_language().await;
+    #[test]
+    fn test_convert_field_tag_column() {
         let row = make_describe_row("location", "NCHAR", "TAG");
-        let field = TdengineImpl::convert_field(&row);
+        let field = TdengineImpl::convert_field("t", &row, &field_types());
         assert_eq!(field.field_name, "location");
         assert_eq!(field.field_type, "String");
         assert_eq!(field.is_null, 1);
-        assert_eq!(field.comment, "[TAG]");
+        assert!(field.is_tag);
+        assert_eq!(field.comment, "");
     }
 
-    #[tokio::test]
-    async fn test_convert_field_camel_case() {
-        init_language().await;
+    #[test]
+    fn test_convert_field_camel_case() {
         let row = make_describe_row("group_id", "INT", "TAG");
-        let field = TdengineImpl::convert_field(&row);
+        let field = TdengineImpl::convert_field("t", &row, &field_types());
         assert_eq!(field.field_name, "group_id");
         assert_eq!(field.FieldName, "GroupId");
         assert_eq!(field.fieldName, "groupId");
     }
 
-    // ========== build_tag_keys tests ==========
+    // ========== table_kind tests ==========
 
     #[test]
-    fn test_build_tag_keys_with_tags() {
+    fn test_table_kind() {
         let rows = vec![
             make_describe_row("ts", "TIMESTAMP", ""),
             make_describe_row("current", "FLOAT", ""),
             make_describe_row("location", "NCHAR", "TAG"),
             make_describe_row("group_id", "INT", "TAG"),
         ];
-        let keys = TdengineImpl::build_tag_keys(&rows);
-        assert_eq!(
-            keys,
-            vec![vec!["location".to_string(), "group_id".to_string()]]
-        );
-    }
-
-    #[test]
-    fn test_build_tag_keys_no_tags() {
+        assert_eq!(TdengineImpl::table_kind(&rows, true), TableKind::Supertable);
+        assert_eq!(TdengineImpl::table_kind(&rows, false), TableKind::Child);
         let rows = vec![
             make_describe_row("ts", "TIMESTAMP", ""),
             make_describe_row("value", "DOUBLE", ""),
         ];
-        let keys = TdengineImpl::build_tag_keys(&rows);
-        assert!(keys.is_empty());
+        assert_eq!(TdengineImpl::table_kind(&rows, false), TableKind::Normal);
     }
 
+    // ========== protocol_feature tests ==========
+
     #[test]
-    fn test_build_tag_keys_empty() {
-        let rows: Vec<TdengineDescribeRow> = vec![];
-        let keys = TdengineImpl::build_tag_keys(&rows);
-        assert!(keys.is_empty());
+    fn test_protocol_feature() {
+        assert_eq!(
+            TdengineImpl::protocol_feature("taos+ws://localhost:6041"),
+            ("taos+ws", "tdengine")
+        );
+        assert_eq!(
+            TdengineImpl::protocol_feature("ws://localhost:6041/power"),
+            ("ws", "tdengine")
+        );
+        assert_eq!(
+            TdengineImpl::protocol_feature("taos://localhost:6030"),
+            ("taos", "taos-native")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_init_protocol() {
+        let config = |db_url: &str| CustomConfig {
+            db_url: db_url.to_string(),
+            ..Default::default()
+        };
+        // 连接池在取连接时才连接, WebSocket 协议只需要 tdengine
+        assert!(
+            TdengineImpl::init(config("ws://localhost:6041"))
+                .await
+                .is_ok()
+        );
+        let native = TdengineImpl::init(config("taos://localhost:6030")).await;
+        // 启用 taos-native 时是否成功取决于能否加载 libtaos
+        assert_eq!(
+            matches!(
+                native,
+                Err(crate::error::Error::TaosFeature(_, "taos-native"))
+            ),
+            !cfg!(feature = "taos-native")
+        );
     }
 }
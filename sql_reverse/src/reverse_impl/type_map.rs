@@ -0,0 +1,200 @@
+use crate::error::Result;
+#[cfg(feature = "clickhouse")]
+use crate::reverse_impl::clickhouse_impl;
+#[cfg(feature = "mysql")]
+use crate::reverse_impl::mysql_impl;
+#[cfg(feature = "postgres")]
+use crate::reverse_impl::postgres_impl;
+#[cfg(feature = "sqlite")]
+use crate::reverse_impl::sqlite_impl;
+#[cfg(feature = "tdengine")]
+use crate::reverse_impl::tdengine_impl;
+use crate::table::Field;
+use crate::template::lang::Lang;
+use crate::template::template_type::TemplateType;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Mutex;
+
+/// 数据库字段类型到 Rust 类型的映射, key 为匹配数据库字段类型的正则表达式, 格式同 export 导出的 json
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TypeMap(BTreeMap<String, String>);
+
+impl TypeMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 数据库的默认类型映射
+    pub fn defaults(template_type: TemplateType) -> Self {
+        Self(field_type_map(template_type).clone())
+    }
+
+    /// 读取 json 格式的类型映射, 例如 sql_reverse export 导出的 default_mysql.json
+    pub async fn load(filename: &str) -> Result<Self> {
+        let s = tokio::fs::read_to_string(filename).await?;
+        Ok(serde_json::from_str(&s)?)
+    }
+
+    /// 添加或替换一条映射, 返回原来的类型
+    pub fn insert(
+        &mut self,
+        pattern: impl Into<String>,
+        rust_type: impl Into<String>,
+    ) -> Option<String> {
+        self.0.insert(pattern.into(), rust_type.into())
+    }
+
+    /// 按正则表达式的顺序查找数据库字段类型对应的 Rust 类型, 未匹配时返回 None
+    pub fn rust_type(&self, field_type: &str) -> Result<Option<String>> {
+        match_field_type(field_type, &self.0)
+    }
+
+    /// 数据库字段类型在目标语言中的类型, 未匹配时返回 None
+    pub fn field_type(&self, field_type: &str, lang: Lang) -> Result<Option<String>> {
+        Ok(self.rust_type(field_type)?.map(|v| lang.field_type(&v)))
+    }
+
+    pub fn as_map(&self) -> &BTreeMap<String, String> {
+        &self.0
+    }
+}
+
+impl From<BTreeMap<String, String>> for TypeMap {
+    fn from(map: BTreeMap<String, String>) -> Self {
+        Self(map)
+    }
+}
+
+impl From<TypeMap> for BTreeMap<String, String> {
+    fn from(map: TypeMap) -> Self {
+        map.0
+    }
+}
+
+/// 数据库的默认类型映射
+fn field_type_map(template_type: TemplateType) -> &'static BTreeMap<String, String> {
+    match template_type {
+        #[cfg(feature = "mysql")]
+        TemplateType::Mysql => &mysql_impl::FIELD_TYPE,
+        #[cfg(feature = "postgres")]
+        TemplateType::Postgres => &postgres_impl::FIELD_TYPE,
+        #[cfg(feature = "sqlite")]
+        TemplateType::Sqlite => &sqlite_impl::FIELD_TYPE,
+        #[cfg(feature = "clickhouse")]
+        TemplateType::Clickhouse => &clickhouse_impl::FIELD_TYPE,
+        #[cfg(feature = "tdengine")]
+        TemplateType::Tdengine => &tdengine_impl::FIELD_TYPE,
+    }
+}
+
+/// 按类型映射查找字段类型, 未匹配时返回 None
+fn match_field_type(
+    field_type: &str,
+    field_type_map: &BTreeMap<String, String>,
+) -> Result<Option<String>> {
+    for (k, v) in field_type_map.iter() {
+        let r = Regex::new(k.trim())?;
+        if r.is_match(field_type) {
+            return Ok(Some(v.to_string()));
+        }
+    }
+    Ok(None)
+}
+
+/// 一次 [`GenStruct::run`](crate::GenStruct::run) 使用的类型映射, 同时记录未匹配的字段类型
+#[derive(Debug, Default)]
+pub struct FieldTypes {
+    map: TypeMap,
+    /// 未匹配到类型映射的字段, (表名.字段名, 数据库字段类型)
+    unmapped: Mutex<BTreeSet<(String, String)>>,
+}
+
+impl FieldTypes {
+    pub fn new(map: TypeMap) -> Self {
+        Self {
+            map,
+            unmapped: Mutex::new(BTreeSet::new()),
+        }
+    }
+
+    /// 按类型映射查找字段类型, 未匹配时返回 None
+    pub fn match_field_type(&self, field_type: &str) -> Result<Option<String>> {
+        self.map.rust_type(field_type)
+    }
+
+    /// 按类型映射查找字段类型, 未匹配时记录并使用 String, column 为 表名.字段名
+    pub fn field_type(&self, field_type: &str, column: &str) -> Result<String> {
+        if let Some(v) = self.match_field_type(field_type)? {
+            return Ok(v);
+        }
+        self.record_unmapped(column, field_type);
+        Ok(String::from("String"))
+    }
+
+    /// 记录未匹配的字段类型, 例如 Rust 类型无法对应 diesel::sql_types 的字段
+    pub fn record_unmapped(&self, column: &str, field_type: &str) {
+        self.unmapped
+            .lock()
+            .unwrap()
+            .insert((column.to_string(), field_type.to_string()));
+    }
+
+    /// 按类型映射设置表中所有字段的类型
+    pub fn map_fields(&self, table_name: &str, fields: &mut [Field]) -> Result<()> {
+        for field in fields.iter_mut() {
+            let column = format!("{}.{}", table_name, field.column_name);
+            field.field_type = self.field_type(&field.database_field_type, &column)?;
+        }
+        Ok(())
+    }
+
+    /// 未匹配的字段类型, 格式为 表名.字段名: 数据库字段类型
+    pub fn unmapped(&self) -> Vec<String> {
+        self.unmapped
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(column, field_type)| format!("{}: {}", column, field_type))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_type_map() {
+        let mut map = TypeMap::new();
+        map.insert("^int$", "i32");
+        map.insert("^varchar", "String");
+        assert_eq!(map.rust_type("int").unwrap(), Some("i32".to_string()));
+        assert_eq!(
+            map.rust_type("varchar(255)").unwrap(),
+            Some("String".to_string())
+        );
+        assert_eq!(map.rust_type("blob").unwrap(), None);
+        assert_eq!(
+            map.field_type("int", Lang::TypeScript).unwrap(),
+            Some(Lang::TypeScript.field_type("i32"))
+        );
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(json, r#"{"^int$":"i32","^varchar":"String"}"#);
+        assert_eq!(serde_json::from_str::<TypeMap>(&json).unwrap(), map);
+    }
+
+    #[test]
+    fn test_field_types() {
+        let mut map = TypeMap::new();
+        map.insert("^int$", "i32");
+        let field_types = FieldTypes::new(map);
+        assert_eq!(field_types.field_type("int", "t.a").unwrap(), "i32");
+        assert_eq!(field_types.field_type("point", "t.b").unwrap(), "String");
+        assert_eq!(field_types.unmapped(), ["t.b: point"]);
+        // 每次 run 使用新的 FieldTypes, 不会带上之前未匹配的字段
+        assert!(FieldTypes::default().unmapped().is_empty());
+    }
+}
@@ -7,6 +7,17 @@ pub struct ClickhouseTable {
     pub comment: String,
 }
 
+/// Row type for system.tables engine query results
+#[derive(Debug, Clone, clickhouse::Row, Deserialize)]
+pub struct ClickhouseEngine {
+    pub engine: String,
+    pub engine_full: String,
+    pub partition_key: String,
+    pub sorting_key: String,
+    pub primary_key: String,
+    pub sampling_key: String,
+}
+
 /// Row type for system.columns query results
 #[derive(Debug, Clone, clickhouse::Row, Deserialize)]
 pub struct ClickhouseField {
@@ -14,8 +25,7 @@ pub struct ClickhouseField {
     pub field_type: String,
     pub comment: String,
     pub default_expression: String,
-    /// "DEFAULT", "MATERIALIZED", "ALIAS", or empty
-    #[allow(dead_code)]
+    /// "DEFAULT", "MATERIALIZED", "ALIAS", "EPHEMERAL", or empty
     pub default_kind: String,
     pub is_in_primary_key: u8,
     pub is_in_sorting_key: u8,
@@ -1,21 +1,29 @@
+#[cfg(feature = "clickhouse")]
 pub mod clickhouse;
+#[cfg(feature = "mysql")]
 pub mod mysql;
+#[cfg(feature = "postgres")]
 pub mod postgres;
+#[cfg(feature = "sqlite")]
 pub mod sqlite;
+#[cfg(feature = "tdengine")]
 pub mod tdengine;
 
+use crate::reverse_impl::common::SoftDeleteConfig;
 use crate::template::kit::Kit;
-use crate::template::render::Render;
+use crate::template::template_type::TemplateType;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct Table2Comment {
     pub table_name: String,
     pub table_comment: Option<String>,
 }
 
 /// sql 表
-#[derive(Serialize, Clone, Default, Debug)]
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+#[non_exhaustive]
 pub struct Table {
     /// 表名
     pub table_name: String,
@@ -29,15 +37,161 @@ pub struct Table {
     pub index_key: Vec<Vec<String>>,
     /// 唯一索引
     pub unique_key: Vec<Vec<String>>,
+    /// 主键, 无法获取时为空
+    pub primary_key: Vec<String>,
+    /// 外键
+    pub foreign_keys: Vec<ForeignKey>,
+    /// 软删除, 表中不存在软删除字段时为空
+    pub soft_delete: Option<SoftDelete>,
+    /// 模块名(目标语言标识符, 关键字已转义), 去掉原始标识符前缀后用作文件名
+    pub module_name: String,
+    /// SQLite WITHOUT ROWID 表, 插入后没有 last_insert_rowid
+    pub without_rowid: bool,
+    /// SQLite rowid 的别名字段(INTEGER PRIMARY KEY), 插入 NULL 时自动分配, 没有时为空
+    pub rowid_alias: Option<String>,
+    /// 字段类型中生成的枚举和结构体, 目前用于 ClickHouse 的 Enum8/Enum16、具名 Tuple 和 Nested
+    pub custom_types: Vec<CustomType>,
+    /// 表引擎信息, 目前用于 ClickHouse, 其他数据库为空
+    pub engine: Option<Engine>,
+    /// TDengine 数据库的时间精度(ms/us/ns), 其他数据库为空
+    pub time_precision: Option<String>,
+    /// TDengine 表类型, 其他数据库为空
+    pub kind: Option<TableKind>,
 }
 
-impl Render for Table {}
+impl Kit for Table {}
+
+/// 外键
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+#[non_exhaustive]
+pub struct ForeignKey {
+    /// 本表字段名
+    pub column: String,
+    /// 引用的表名
+    pub referenced_table: String,
+    /// 引用的字段名
+    pub referenced_column: String,
+}
+
+/// TDengine 表类型
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum TableKind {
+    /// 超级表, 字段包含标签列
+    Supertable,
+    /// 普通表, 没有标签列
+    Normal,
+    /// 子表, 标签值在建表时确定
+    Child,
+}
+
+/// ClickHouse 表引擎信息, 各项为空字符串表示未设置
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+#[non_exhaustive]
+pub struct Engine {
+    /// 引擎名, 例如 MergeTree, ReplacingMergeTree
+    pub name: String,
+    /// 完整的引擎定义
+    pub engine_full: String,
+    /// PARTITION BY
+    pub partition_key: String,
+    /// ORDER BY
+    pub sorting_key: String,
+    /// PRIMARY KEY
+    pub primary_key: String,
+    /// SAMPLE BY
+    pub sampling_key: String,
+    /// 表级 TTL
+    pub ttl: String,
+}
+
+/// 根据字段类型生成的枚举或结构体
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+#[non_exhaustive]
+pub struct CustomType {
+    /// 类型名
+    pub name: String,
+    /// 数据库类型
+    pub database_type: String,
+    /// 枚举的底层整数类型(i8/i16), 结构体为空
+    pub repr: Option<String>,
+    /// 枚举变体或结构体字段
+    pub members: Vec<CustomTypeMember>,
+}
+
+/// 枚举变体或结构体字段
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+#[non_exhaustive]
+pub struct CustomTypeMember {
+    /// 标识符
+    pub name: String,
+    /// 数据库中的名称, 枚举为取值文本, 结构体为元素名
+    pub original: String,
+    /// 枚举为取值, 结构体为字段类型
+    pub value: String,
+}
+
+/// 软删除
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+#[non_exhaustive]
+pub struct SoftDelete {
+    /// 软删除字段名
+    pub column: String,
+    /// 删除时的赋值语句, 例如: is_deleted = 1
+    pub delete_set: String,
+    /// 恢复时的赋值语句, 例如: is_deleted = 0
+    pub restore_set: String,
+    /// 未删除数据的过滤条件, 例如: is_deleted = 0, deleted_at IS NULL
+    pub active_condition: String,
+    /// 已删除数据的过滤条件, 例如: is_deleted <> 0, deleted_at IS NOT NULL
+    pub deleted_condition: String,
+}
+
+impl SoftDelete {
+    /// 配置启用、表中存在软删除字段且数据库支持软删除时返回 Some
+    pub fn new(
+        config: &SoftDeleteConfig,
+        fields: &[Field],
+        template_type: &TemplateType,
+    ) -> Option<Self> {
+        if !config.enabled
+            || !template_type.supports_soft_delete()
+            || !fields.iter().any(|v| v.column_name == config.column)
+        {
+            return None;
+        }
+        let quoted = template_type.quote(&config.column);
+        let (active_condition, deleted_condition) =
+            if config.active_value.eq_ignore_ascii_case("null") {
+                (
+                    format!("{} IS NULL", quoted),
+                    format!("{} IS NOT NULL", quoted),
+                )
+            } else {
+                (
+                    format!("{} = {}", quoted, config.active_value),
+                    format!("{} <> {}", quoted, config.active_value),
+                )
+            };
+        Some(SoftDelete {
+            delete_set: format!("{} = {}", quoted, config.deleted_value),
+            restore_set: format!("{} = {}", quoted, config.active_value),
+            active_condition,
+            deleted_condition,
+            column: config.column.to_owned(),
+        })
+    }
+}
 
 /// sql 字段
 #[allow(non_snake_case)]
-#[derive(Serialize, Clone, Default, Debug)]
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+#[non_exhaustive]
 pub struct Field {
-    /// 数据库字段名
+    /// 数据库字段名(原始列名)
+    pub column_name: String,
+    /// 字段名(目标语言标识符, 关键字已转义)
     pub field_name: String,
     /// 首字母大写驼峰字段名
     pub FieldName: String,
@@ -53,31 +207,45 @@ pub struct Field {
     pub is_null: u8,
     /// 默认值
     pub default: Option<String>,
+    /// 转义前的字段名, 仅在使用 rename 方式转义保留字时有值, 模板据此生成重命名属性
+    pub rename: Option<String>,
+    /// 默认值类型, 目前用于 ClickHouse 的 DEFAULT/MATERIALIZED/ALIAS/EPHEMERAL
+    pub default_kind: Option<String>,
+    /// 由数据库计算的字段(ClickHouse MATERIALIZED/ALIAS), 插入时不能写入
+    pub generated: bool,
+    /// TDengine 标签列
+    pub is_tag: bool,
 }
 
 impl Table {
+    /// 手动构建表结构, 例如在库中直接调用 [`Renderer::render`](crate::Renderer::render),
+    /// 索引、主键等其他字段为空, 可以在创建后设置; 结构体名、模块名和字段名由 render 生成
+    pub fn new(table_name: impl Into<String>, fields: Vec<Field>) -> Self {
+        Table {
+            table_name: table_name.into(),
+            fields,
+            ..Default::default()
+        }
+    }
+
     pub async fn skip_fields(&self, skip_fields: Vec<String>) -> Table {
         let mut fields = vec![];
         for field in self.fields.iter() {
             let mut flag = true;
             for keys in &self.index_key {
-                if keys.contains(&field.field_name) {
+                if keys.contains(&field.column_name) {
                     fields.push(field.to_owned());
                     flag = false;
                     continue;
                 }
             }
-            if !skip_fields.contains(&field.field_name) && flag {
+            if !skip_fields.contains(&field.column_name) && flag {
                 fields.push(field.to_owned());
             }
         }
         Table {
-            table_name: self.table_name.to_owned(),
-            struct_name: self.struct_name.to_owned(),
             fields,
-            comment: self.comment.to_owned(),
-            index_key: self.index_key.to_owned(),
-            unique_key: self.unique_key.to_owned(),
+            ..self.clone()
         }
     }
 
@@ -86,25 +254,177 @@ impl Table {
         for field in self.fields.iter() {
             let mut flag = true;
             for keys in &self.index_key {
-                if keys.contains(&field.field_name) {
+                if keys.contains(&field.column_name) {
                     fields.push(field.to_owned());
                     flag = false;
                     continue;
                 }
             }
-            if contain_fields.contains(&field.field_name) && flag {
+            if contain_fields.contains(&field.column_name) && flag {
                 fields.push(field.to_owned());
             }
         }
         Table {
-            table_name: self.table_name.to_owned(),
-            struct_name: self.struct_name.to_owned(),
             fields,
-            comment: self.comment.to_owned(),
-            index_key: self.index_key.to_owned(),
-            unique_key: self.unique_key.to_owned(),
+            ..self.clone()
+        }
+    }
+}
+
+impl Table {
+    /// Diesel/SeaORM 要求每张表都有主键且主键非空, 未获取到主键时依次使用第一个唯一索引和第一个字段,
+    /// 没有字段的表(例如 sqlite_sequence)无法生成, 直接跳过
+    pub fn with_primary_key(tables: &[Table]) -> Vec<Table> {
+        let mut tables: Vec<Table> = tables
+            .iter()
+            .filter(|v| !v.fields.is_empty())
+            .cloned()
+            .collect();
+        for table in tables.iter_mut() {
+            if table.primary_key.is_empty() {
+                table.primary_key = match table.unique_key.first() {
+                    Some(keys) => keys.to_owned(),
+                    None => table
+                        .fields
+                        .iter()
+                        .take(1)
+                        .map(|v| v.column_name.to_owned())
+                        .collect(),
+                };
+            }
+            for field in table.fields.iter_mut() {
+                if table.primary_key.contains(&field.column_name) {
+                    field.is_null = 0;
+                }
+            }
+        }
+        tables
+    }
+
+    /// 主键和唯一索引中的字段(去重), update 和 upsert 不修改这些字段
+    pub fn key_columns(&self) -> Vec<&str> {
+        let mut columns: Vec<&str> = vec![];
+        for column in self
+            .primary_key
+            .iter()
+            .chain(self.unique_key.iter().flatten())
+        {
+            if !columns.contains(&column.as_str()) {
+                columns.push(column);
+            }
+        }
+        columns
+    }
+
+    /// update_by_* 和部分更新结构体可以修改的字段: 主键、唯一索引和软删除字段以外的字段
+    pub fn update_fields(&self) -> Vec<&Field> {
+        let key_columns = self.key_columns();
+        self.fields
+            .iter()
+            .filter(|v| !key_columns.contains(&v.column_name.as_str()))
+            .filter(|v| {
+                self.soft_delete
+                    .as_ref()
+                    .is_none_or(|soft_delete| soft_delete.column != v.column_name)
+            })
+            .collect()
+    }
+}
+
+impl Field {
+    /// 手动构建字段, field_type 为 Rust 类型, 可空、注释等其他字段可以在创建后设置
+    pub fn new(
+        column_name: impl Into<String>,
+        database_field_type: impl Into<String>,
+        field_type: impl Into<String>,
+    ) -> Self {
+        Field {
+            column_name: column_name.into(),
+            database_field_type: database_field_type.into(),
+            field_type: field_type.into(),
+            ..Default::default()
         }
     }
 }
 
 impl Kit for Field {}
+
+#[cfg(all(test, feature = "mysql", feature = "postgres"))]
+mod tests {
+    use super::*;
+
+    fn make_field(column_name: &str) -> Field {
+        Field {
+            column_name: column_name.to_string(),
+            field_name: column_name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_soft_delete_default_flag() {
+        let fields = vec![make_field("id"), make_field("is_deleted")];
+        let soft_delete =
+            SoftDelete::new(&SoftDeleteConfig::default(), &fields, &TemplateType::Mysql).unwrap();
+        assert_eq!(soft_delete.delete_set, "`is_deleted` = 1");
+        assert_eq!(soft_delete.restore_set, "`is_deleted` = 0");
+        assert_eq!(soft_delete.active_condition, "`is_deleted` = 0");
+        assert_eq!(soft_delete.deleted_condition, "`is_deleted` <> 0");
+    }
+
+    #[test]
+    fn test_soft_delete_timestamp() {
+        let config = SoftDeleteConfig {
+            enabled: true,
+            column: "deleted_at".to_string(),
+            deleted_value: "CURRENT_TIMESTAMP".to_string(),
+            active_value: "NULL".to_string(),
+        };
+        let fields = vec![make_field("id"), make_field("deleted_at")];
+        let soft_delete = SoftDelete::new(&config, &fields, &TemplateType::Postgres).unwrap();
+        assert_eq!(
+            soft_delete.delete_set,
+            r#""deleted_at" = CURRENT_TIMESTAMP"#
+        );
+        assert_eq!(soft_delete.restore_set, r#""deleted_at" = NULL"#);
+        assert_eq!(soft_delete.active_condition, r#""deleted_at" IS NULL"#);
+        assert_eq!(soft_delete.deleted_condition, r#""deleted_at" IS NOT NULL"#);
+    }
+
+    #[test]
+    fn test_soft_delete_backends() {
+        let fields = vec![make_field("id"), make_field("is_deleted")];
+        let config = SoftDeleteConfig::default();
+        for template_type in [
+            TemplateType::Mysql,
+            TemplateType::Postgres,
+            #[cfg(feature = "sqlite")]
+            TemplateType::Sqlite,
+            #[cfg(feature = "clickhouse")]
+            TemplateType::Clickhouse,
+        ] {
+            assert!(
+                SoftDelete::new(&config, &fields, &template_type).is_some(),
+                "{:?}",
+                template_type
+            );
+        }
+        // TDengine 没有 UPDATE 语句, is_deleted 只是普通字段
+        #[cfg(feature = "tdengine")]
+        assert!(SoftDelete::new(&config, &fields, &TemplateType::Tdengine).is_none());
+    }
+
+    #[test]
+    fn test_soft_delete_missing_column_or_disabled() {
+        let fields = vec![make_field("id")];
+        assert!(
+            SoftDelete::new(&SoftDeleteConfig::default(), &fields, &TemplateType::Mysql).is_none()
+        );
+        let config = SoftDeleteConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        let fields = vec![make_field("id"), make_field("is_deleted")];
+        assert!(SoftDelete::new(&config, &fields, &TemplateType::Mysql).is_none());
+    }
+}
@@ -1,5 +1,3 @@
-use crate::keywords::LANGUAGE;
-use crate::reverse_impl::postgres_impl;
 use crate::table::{Field, Table2Comment};
 use crate::template::kit::Kit;
 use inflector::Inflector;
@@ -14,22 +12,22 @@ impl FromRow<'_, PgRow> for Field {
         let is_null: i64 = row.try_get("is_null").unwrap_or_default();
         let field_name_camel_case = field_name.clone().to_camel_case();
         let first_char_uppercase_field_name = Self::first_char_to_uppercase(&field_name_camel_case);
-        let field_type = Self::get_field_type(
-            &database_field_type,
-            &field_name,
-            &postgres_impl::FIELD_TYPE.read().unwrap(),
-        )
-        .unwrap_or_default();
         let default = row.try_get("default_value").ok();
         Ok(Field {
-            field_name: LANGUAGE.check_field_name(&field_name),
+            field_name: field_name.clone(),
+            column_name: field_name,
             FieldName: first_char_uppercase_field_name,
-            fieldName: LANGUAGE.check_field_name(&field_name_camel_case),
+            fieldName: field_name_camel_case,
             database_field_type,
-            field_type,
+            // 按表名.字段名记录未匹配的类型, 在 gen_templates 中映射
+            field_type: String::new(),
             comment,
             is_null: is_null as u8,
             default,
+            rename: None,
+            default_kind: None,
+            generated: false,
+            is_tag: false,
         })
     }
 }
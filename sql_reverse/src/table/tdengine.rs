@@ -14,6 +14,13 @@ pub struct TdengineNormalTable {
     pub table_comment: Option<String>,
 }
 
+/// Row type for information_schema.ins_databases query results
+#[derive(Debug, Clone, Deserialize)]
+pub struct TdengineDatabase {
+    /// "ms", "us" or "ns"
+    pub precision: String,
+}
+
 /// Row type for DESCRIBE command results
 #[derive(Debug, Clone, Deserialize)]
 pub struct TdengineDescribeRow {
@@ -1,17 +1,97 @@
-use std::sync::{LazyLock, RwLock};
-
-pub static CLICKHOUSE_TEMPLATE: LazyLock<RwLock<&str>> = LazyLock::new(|| {
-    RwLock::new(
-        r#"
+pub static CLICKHOUSE_TEMPLATE: &str = r#"
 use serde::{Deserialize, Serialize};
 use clickhouse::Row;
-use super::Result;
+use super::{% if table.index_key %}{Page, Result}{% else %}Result{% endif %};
 use super::CLICKHOUSE_CLIENT;
 
-pub const TABLE_NAME: &str = "{{table.table_name}}";
+pub const TABLE_NAME: &str = "`{{table.table_name}}`";
+
+pub const FIELDS: &str = "{%- for field in table.fields -%}`{{field.column_name}}`{%- if loop.last == false -%},{%- endif -%}{%- endfor -%}";
+
+{% for field in table.fields -%}
+pub const COLUMN_{{ field.field_name | replace(from="r#", to="") | upper }}: &str = "{{ field.column_name | addslashes }}";
+{% endfor %}
+/// Columns of `{{ table.table_name }}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum {{ table.struct_name }}Column {
+{%- for field in table.fields %}
+    {{ field.FieldName }},
+{%- endfor %}
+}
+
+impl {{ table.struct_name }}Column {
+    pub const ALL: [Self; {{ table.fields | length }}] = [
+{%- for field in table.fields %}
+        Self::{{ field.FieldName }},
+{%- endfor %}
+    ];
+
+    /// Raw column name.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+{%- for field in table.fields %}
+            Self::{{ field.FieldName }} => COLUMN_{{ field.field_name | replace(from="r#", to="") | upper }},
+{%- endfor %}
+        }
+    }
+
+    /// Column name quoted as an SQL identifier.
+    pub const fn quoted(&self) -> &'static str {
+        match self {
+{%- for field in table.fields %}
+            Self::{{ field.FieldName }} => "`{{ field.column_name | addslashes }}`",
+{%- endfor %}
+        }
+    }
+}
+
+impl std::fmt::Display for {{ table.struct_name }}Column {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+{%- for t in table.custom_types %}
+/// `{{ t.database_type }}`
+{%- if t.repr %}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
+#[repr({{ t.repr }})]
+pub enum {{ t.name }} {
+{%- for m in t.members %}
+    /// '{{ m.original }}'
+    {{ m.name }} = {{ m.value }},
+{%- endfor %}
+}
+{%- else %}
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct {{ t.name }} {
+{%- for m in t.members %}
+{%- if m.name | replace(from="r#", to="") != m.original %}
+    #[serde(rename = "{{ m.original | addslashes }}")]
+{%- endif %}
+    pub {{ m.name }}: {{ m.value }},
+{%- endfor %}
+}
+{%- endif %}
+{% endfor %}
+{%- set_global has_map = false %}
+{%- for v in table.fields %}{% if v.field_type is starting_with("std::collections::HashMap") %}{% set_global has_map = true %}{% endif %}{% endfor %}
+{%- if has_map %}
+/// Map columns are sent as Array(Tuple(K, V)) in RowBinary.
+mod ch_map {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+    use std::hash::Hash;
 
-pub const FIELDS: &str = "{%- for field in table.fields -%}{{field.field_name}}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%}";
+    pub fn serialize<K: Serialize, V: Serialize, S: Serializer>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(map.iter())
+    }
 
+    pub fn deserialize<'de, K: Deserialize<'de> + Eq + Hash, V: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<HashMap<K, V>, D::Error> {
+        Ok(Vec::<(K, V)>::deserialize(deserializer)?.into_iter().collect())
+    }
+}
+{% endif %}
 {% if table.comment -%}
 	/// {{ table.comment }}
 {% endif -%}
@@ -21,12 +101,35 @@ pub const FIELDS: &str = "{%- for field in table.fields -%}{{field.field_name}}{
 {% for index in table.index_key -%}
     /// SortingKey：{{index}}
 {% endfor -%}
+{% if table.engine -%}
+    /// ENGINE = {{ table.engine.name }}
+{% if table.engine.partition_key %}    /// PARTITION BY {{ table.engine.partition_key }}
+{% endif -%}
+{% if table.engine.sorting_key %}    /// ORDER BY {{ table.engine.sorting_key }}
+{% endif -%}
+{% if table.engine.primary_key and table.engine.primary_key != table.engine.sorting_key %}    /// PRIMARY KEY {{ table.engine.primary_key }}
+{% endif -%}
+{% if table.engine.sampling_key %}    /// SAMPLE BY {{ table.engine.sampling_key }}
+{% endif -%}
+{% if table.engine.ttl %}    /// TTL {{ table.engine.ttl }}
+{% endif -%}
+{% endif -%}
 #[derive(Debug, Clone, Serialize, Deserialize, Row)]
 pub struct {{ table.struct_name }} {
 {%- for v in table.fields %}
 	{% if v.comment -%}
 	    /// {{ v.comment }} {% if v.database_field_type %} field_type: {{ v.database_field_type }}{% endif %}{% if v.default %} default: {{ v.default }}{% endif %} {% if v.default == '' %} default: ''{% endif %}
 	{% endif -%}
+	{% if v.generated -%}
+	    /// {{ v.default_kind }} {{ v.default }}, computed by the server and skipped on insert
+	    #[serde(skip_serializing)]
+	{% endif -%}
+	{% if v.column_name != v.field_name -%}
+	    #[serde(rename = "{{ v.column_name | addslashes }}")]
+	{% endif -%}
+	{% if v.field_type is starting_with("std::collections::HashMap") -%}
+	    #[serde(with = "ch_map")]
+	{% endif -%}
 	{% if v.is_null == 1 -%}
     	pub {{ v.field_name }}: Option<{{ v.field_type }}>,
     {%- else -%}
@@ -47,8 +150,17 @@ impl {{table.struct_name}} {
         Ok(())
     }
 
+    pub async fn insert_many(rows: &[Self]) -> Result<()> {
+    	let mut insert = CLICKHOUSE_CLIENT.insert(TABLE_NAME)?;
+    	for row in rows {
+    	    insert.write(row).await?;
+    	}
+    	insert.end().await?;
+        Ok(())
+    }
+
     pub async fn select_all() -> Result<Vec<Self>> {
-        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} {% for v in table.fields -%}{%- if v.field_name == 'is_deleted' -%} WHERE is_deleted = 0 {%- endif -%}{%- endfor -%}");
+        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} {%- if table.soft_delete %} WHERE {{ table.soft_delete.active_condition | addslashes }}{%- endif -%}");
         let data = CLICKHOUSE_CLIENT.query(&sql).fetch_all::<Self>().await?;
         Ok(data)
     }
@@ -56,12 +168,12 @@ impl {{table.struct_name}} {
 {% for indexes in table.unique_key %}
 
     pub async fn select_optional_by {%- for index in indexes -%}
-                        _{{index}}
-    {%- endfor %}({%- for index in indexes -%}{{index}}: {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%})->Result<Option<Self>>{
-        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} {{index}} = ? {% else %} {{index}} = ? AND {%- endif -%}{%- endfor -%}{%- for v in table.fields -%}{%- if v.field_name == 'is_deleted' -%} AND is_deleted = 0 {%- endif -%}{%- endfor -%} LIMIT 1");
+                        _{{index | ident}}
+    {%- endfor %}({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%})->Result<Option<Self>>{
+        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} `{{index}}` = ? {% else %} `{{index}}` = ? AND {%- endif -%}{%- endfor -%}{%- if table.soft_delete %} AND {{ table.soft_delete.active_condition | addslashes }}{%- endif -%} LIMIT 1");
         let data = CLICKHOUSE_CLIENT.query(&sql)
             {% for index in indexes -%}
-                .bind({{index}})
+                {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind({{v.field_name}}){%- endif -%}{%- endfor -%}
             {% endfor -%}
             .fetch_optional::<Self>()
             .await?;
@@ -73,12 +185,12 @@ impl {{table.struct_name}} {
 {% for indexes in table.unique_key %}
 
     pub async fn select_one_by {%- for index in indexes -%}
-                        _{{index}}
-    {%- endfor %}({%- for index in indexes -%}{{index}}: {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%})->Result<Self>{
-        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} {{index}} = ? {% else %} {{index}} = ? AND {%- endif -%}{%- endfor -%}{%- for v in table.fields -%}{%- if v.field_name == 'is_deleted' -%} AND is_deleted = 0 {%- endif -%}{%- endfor -%} LIMIT 1");
+                        _{{index | ident}}
+    {%- endfor %}({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%})->Result<Self>{
+        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} `{{index}}` = ? {% else %} `{{index}}` = ? AND {%- endif -%}{%- endfor -%}{%- if table.soft_delete %} AND {{ table.soft_delete.active_condition | addslashes }}{%- endif -%} LIMIT 1");
         let data = CLICKHOUSE_CLIENT.query(&sql)
             {% for index in indexes -%}
-                .bind({{index}})
+                {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind({{v.field_name}}){%- endif -%}{%- endfor -%}
             {% endfor -%}
             .fetch_one::<Self>()
             .await?;
@@ -90,28 +202,53 @@ impl {{table.struct_name}} {
 {% for indexes in table.index_key %}
 
     pub async fn select_many_by{%- for index in indexes -%}
-                        _{{index}}
-    {%- endfor -%}_by_page({%- for index in indexes -%}{{index}}: {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%}page_no: u64, page_size: u64)->Result<Vec<Self>>{
-        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} {{index}} = ? {% else %} {{index}} = ? AND {%- endif -%}{%- endfor -%} {%- for v in table.fields -%}{%- if v.field_name == 'is_deleted' -%} AND is_deleted = 0 {%- endif -%}{%- endfor %} LIMIT {} OFFSET {}", page_size, (page_no - 1) * page_size);
+                        _{{index | ident}}
+    {%- endfor -%}_by_page({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%}page_no: u64, page_size: u64)->Result<Vec<Self>>{
+        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} `{{index}}` = ? {% else %} `{{index}}` = ? AND {%- endif -%}{%- endfor -%} {%- if table.soft_delete %} AND {{ table.soft_delete.active_condition | addslashes }}{%- endif %} ORDER BY {% for key in indexes | concat(with=table.primary_key) | unique %}`{{key}}`{% if not loop.last %}, {% endif %}{% endfor %} LIMIT {} OFFSET {}", page_size, page_no.saturating_sub(1) * page_size);
         let data = CLICKHOUSE_CLIENT.query(&sql)
             {% for index in indexes -%}
-                .bind({{index}})
+                {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind({{v.field_name}}){%- endif -%}{%- endfor -%}
             {% endfor -%}
             .fetch_all::<Self>()
             .await?;
         Ok(data)
     }
+
+    pub async fn count_by{%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor -%}({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%})->Result<u64>{
+        let sql = format!("SELECT count() FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} `{{index}}` = ? {% else %} `{{index}}` = ? AND {%- endif -%}{%- endfor -%} {%- if table.soft_delete %} AND {{ table.soft_delete.active_condition | addslashes }}{%- endif %}");
+        let data = CLICKHOUSE_CLIENT.query(&sql)
+            {% for index in indexes -%}
+                {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind({{v.field_name}}){%- endif -%}{%- endfor -%}
+            {% endfor -%}
+            .fetch_one::<u64>()
+            .await?;
+        Ok(data)
+    }
+
+    pub async fn select_page_by{%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor -%}({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%}page_no: u64, page_size: u64)->Result<Page<Self>>{
+        let total = Self::count_by{%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor -%}({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}{%- endif -%}{%- endfor -%}, {%- endfor -%}).await?;
+        let items = Self::select_many_by{%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor -%}_by_page({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}{%- endif -%}{%- endfor -%}, {%- endfor -%}page_no, page_size).await?;
+        Ok(Page { items, total, page_no, page_size })
+    }
 {% endfor -%}
 
 {% for indexes in table.index_key %}
 
     pub async fn select_all_by{%- for index in indexes -%}
-                        _{{index}}
-    {%- endfor -%}({%- for index in indexes -%}{{index}}: {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%})->Result<Vec<Self>>{
-        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} {{index}} = ? {% else %} {{index}} = ? AND {%- endif -%}{%- endfor -%} {%- for v in table.fields -%}{%- if v.field_name == 'is_deleted' -%} AND is_deleted = 0 {%- endif -%}{%- endfor -%}");
+                        _{{index | ident}}
+    {%- endfor -%}({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%})->Result<Vec<Self>>{
+        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} `{{index}}` = ? {% else %} `{{index}}` = ? AND {%- endif -%}{%- endfor -%} {%- if table.soft_delete %} AND {{ table.soft_delete.active_condition | addslashes }}{%- endif -%}");
         let data = CLICKHOUSE_CLIENT.query(&sql)
             {% for index in indexes -%}
-                .bind({{index}})
+                {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind({{v.field_name}}){%- endif -%}{%- endfor -%}
             {% endfor -%}
             .fetch_all::<Self>()
             .await?;
@@ -119,17 +256,15 @@ impl {{table.struct_name}} {
     }
 {% endfor -%}
 
-{%- for v in table.fields -%}
-    {%- if v.field_name == 'is_deleted' -%}
 {% for indexes in table.unique_key %}
 
     pub async fn delete_one_by {%- for index in indexes -%}
-                        _{{index}}
-    {%- endfor %}({%- for index in indexes -%}{{index}}: {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%})->Result<()>{
-        let sql = format!("ALTER TABLE {TABLE_NAME} UPDATE is_deleted = 1 WHERE {% for index in indexes -%} {%- if loop.last %} {{index}} = ? {% else %} {{index}} = ? AND {%- endif -%}{%- endfor -%}{%- for v in table.fields -%}{%- if v.field_name == 'is_deleted' -%} AND is_deleted = 0 {%- endif -%}{%- endfor -%}");
+                        _{{index | ident}}
+    {%- endfor %}({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%})->Result<()>{
+        let sql = format!("{% if table.soft_delete %}ALTER TABLE {TABLE_NAME} UPDATE {{ table.soft_delete.delete_set | addslashes }} WHERE {% for index in indexes -%} {%- if loop.last %} `{{index}}` = ? {% else %} `{{index}}` = ? AND {%- endif -%}{%- endfor -%} AND {{ table.soft_delete.active_condition | addslashes }}{% else %}ALTER TABLE {TABLE_NAME} DELETE WHERE {% for index in indexes -%} {%- if loop.last %} `{{index}}` = ? {% else %} `{{index}}` = ? AND {%- endif -%}{%- endfor -%}{% endif %}");
         CLICKHOUSE_CLIENT.query(&sql)
             {% for index in indexes -%}
-                .bind({{index}})
+                {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind({{v.field_name}}){%- endif -%}{%- endfor -%}
             {% endfor -%}
             .execute()
             .await?;
@@ -140,21 +275,51 @@ impl {{table.struct_name}} {
 {% for indexes in table.index_key %}
 
     pub async fn delete_many_by {%- for index in indexes -%}
-                        _{{index}}
-    {%- endfor %}({%- for index in indexes -%}{{index}}: {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%})->Result<()>{
-        let sql = format!("ALTER TABLE {TABLE_NAME} UPDATE is_deleted = 1 WHERE {% for index in indexes -%} {%- if loop.last %} {{index}} = ? {% else %} {{index}} = ? AND {%- endif -%}{%- endfor -%}{%- for v in table.fields -%}{%- if v.field_name == 'is_deleted' -%} AND is_deleted = 0 {%- endif -%}{%- endfor -%}");
+                        _{{index | ident}}
+    {%- endfor %}({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%})->Result<()>{
+        let sql = format!("{% if table.soft_delete %}ALTER TABLE {TABLE_NAME} UPDATE {{ table.soft_delete.delete_set | addslashes }} WHERE {% for index in indexes -%} {%- if loop.last %} `{{index}}` = ? {% else %} `{{index}}` = ? AND {%- endif -%}{%- endfor -%} AND {{ table.soft_delete.active_condition | addslashes }}{% else %}ALTER TABLE {TABLE_NAME} DELETE WHERE {% for index in indexes -%} {%- if loop.last %} `{{index}}` = ? {% else %} `{{index}}` = ? AND {%- endif -%}{%- endfor -%}{% endif %}");
         CLICKHOUSE_CLIENT.query(&sql)
             {% for index in indexes -%}
-                .bind({{index}})
+                {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind({{v.field_name}}){%- endif -%}{%- endfor -%}
             {% endfor -%}
             .execute()
             .await?;
         Ok(())
     }
 {% endfor -%}
-    {%- endif -%}
-{%- endfor -%}
+
+{%- if table.soft_delete -%}
+{% for indexes in table.unique_key %}
+
+    pub async fn restore_one_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%})->Result<()>{
+        let sql = format!("ALTER TABLE {TABLE_NAME} UPDATE {{ table.soft_delete.restore_set | addslashes }} WHERE {% for index in indexes -%} {%- if loop.last %} `{{index}}` = ? {% else %} `{{index}}` = ? AND {%- endif -%}{%- endfor -%} AND {{ table.soft_delete.deleted_condition | addslashes }}");
+        CLICKHOUSE_CLIENT.query(&sql)
+            {% for index in indexes -%}
+                {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind({{v.field_name}}){%- endif -%}{%- endfor -%}
+            {% endfor -%}
+            .execute()
+            .await?;
+        Ok(())
+    }
+{% endfor -%}
+
+{% for indexes in table.index_key %}
+
+    pub async fn restore_many_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%})->Result<()>{
+        let sql = format!("ALTER TABLE {TABLE_NAME} UPDATE {{ table.soft_delete.restore_set | addslashes }} WHERE {% for index in indexes -%} {%- if loop.last %} `{{index}}` = ? {% else %} `{{index}}` = ? AND {%- endif -%}{%- endfor -%} AND {{ table.soft_delete.deleted_condition | addslashes }}");
+        CLICKHOUSE_CLIENT.query(&sql)
+            {% for index in indexes -%}
+                {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind({{v.field_name}}){%- endif -%}{%- endfor -%}
+            {% endfor -%}
+            .execute()
+            .await?;
+        Ok(())
+    }
+{% endfor -%}
+{%- endif -%}
 }
-    "#,
-    )
-});
+    "#;
@@ -0,0 +1,371 @@
+use crate::keywords::Naming;
+use crate::reverse_impl::type_map::FieldTypes;
+use crate::table::{Field, Table};
+use crate::template::kit::snake_identifier;
+use crate::template::template_type::TemplateType;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use tera::{Context, Tera, Value};
+
+pub static DIESEL_TEMPLATE: &str = r#"
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use super::schema::{{ table.module_name }};
+
+{% if table.comment -%}
+/// {{ table.comment }}
+{% endif -%}
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable, Insertable
+{%- if table.fields | length > table.primary_key | length %}, AsChangeset{% endif %})]
+#[diesel(table_name = {{ table.module_name }})]
+#[diesel(primary_key({% for key in table.primary_key %}{{ key | diesel_column(table=table) }}{% if loop.last == false %}, {% endif %}{% endfor %}))]
+#[diesel(check_for_backend({{ backend }}))]
+pub struct {{ table.struct_name }} {
+{%- for v in table.fields %}
+    {%- if v.comment %}
+    /// {{ v.comment }}
+    {%- endif %}
+    pub {{ v.field_name }}: {% if v.is_null == 1 %}Option<{{ v.field_type }}>{% else %}{{ v.field_type }}{% endif %},
+{%- endfor %}
+}
+"#;
+
+/// schema.rs 模板, 每次生成时整体覆盖, 表名和字段名与模型中的模块名和字段名一致, 与数据库中的名称不同时生成 sql_name
+pub static SCHEMA_TEMPLATE: &str = r#"// @generated by sql_reverse, do not edit.
+{% for table in tables %}
+diesel::table! {
+    {%- if table.comment %}
+    /// {{ table.comment }}
+    {%- endif %}
+    {%- if table.table_name != table.module_name | replace(from="r#", to="") %}
+    #[sql_name = "{{ table.table_name | addslashes }}"]
+    {%- endif %}
+    {{ table.module_name }} ({% for key in table.primary_key %}{{ key | diesel_column(table=table) }}{% if loop.last == false %}, {% endif %}{% endfor %}) {
+    {%- for v in table.fields %}
+        {%- if v.comment %}
+        /// {{ v.comment }}
+        {%- endif %}
+        {%- if v.column_name != v.field_name | replace(from="r#", to="") %}
+        #[sql_name = "{{ v.column_name | addslashes }}"]
+        {%- endif %}
+        {{ v.field_name }} -> {{ v | diesel_type }},
+    {%- endfor %}
+    }
+}
+{% endfor %}
+{%- for v in joinables %}
+diesel::joinable!({{ v.child }} -> {{ v.parent }} ({{ v.column }}));
+{%- endfor %}
+{%- if tables | length > 1 %}
+
+diesel::allow_tables_to_appear_in_same_query!(
+{%- for table in tables %}
+    {{ table.module_name }},
+{%- endfor %}
+);
+{%- endif %}
+"#;
+
+/// mod.rs 头部
+pub static MOD_TEMPLATE: &str = r#"
+pub type Result<T> = std::result::Result<T, diesel::result::Error>;
+pub mod schema;
+"#;
+
+/// 按字段的 Rust 类型(已应用类型映射)推导 diesel::sql_types, 无法对应时返回 None,
+/// 同一 Rust 类型对应多种数据库类型时按数据库字段类型区分, 例如 MySQL 的 datetime 和 PostgreSQL 的 jsonb
+pub fn sql_type(template_type: &TemplateType, field: &Field) -> Option<String> {
+    let mysql = template_type.name() == "mysql";
+    let postgres = template_type.name() == "postgres";
+    let database_field_type = field.database_field_type.trim().to_lowercase();
+    let rust_type = field.field_type.trim();
+    let path = rust_type.split('<').next().unwrap_or(rust_type);
+    let name = path.rsplit("::").next().unwrap_or(path);
+    let sql_type = match name {
+        "bool" => "Bool",
+        "i8" if mysql => "TinyInt",
+        "u8" if mysql => "Unsigned<TinyInt>",
+        "i16" => "SmallInt",
+        "u16" if mysql => "Unsigned<SmallInt>",
+        "i32" => "Integer",
+        "u32" if mysql => "Unsigned<Integer>",
+        "i64" => "BigInt",
+        "u64" if mysql => "Unsigned<BigInt>",
+        "f32" => "Float",
+        "f64" => "Double",
+        "String" => "Text",
+        "Vec" if rust_type == "Vec<u8>" => "Binary",
+        "Decimal" | "BigDecimal" => "Numeric",
+        "Date" | "NaiveDate" => "Date",
+        "Time" | "NaiveTime" => "Time",
+        "NaiveDateTime" | "PrimitiveDateTime"
+            if mysql && database_field_type.starts_with("datetime") =>
+        {
+            "Datetime"
+        }
+        "NaiveDateTime" | "PrimitiveDateTime" => "Timestamp",
+        "DateTime" | "OffsetDateTime" if postgres => "Timestamptz",
+        "DateTime" | "OffsetDateTime" => "Timestamp",
+        "Uuid" if postgres => "Uuid",
+        "Value" | "JsonValue" if postgres && database_field_type == "jsonb" => "Jsonb",
+        "Value" | "JsonValue" => "Json",
+        _ => return None,
+    };
+    if field.is_null == 1 {
+        Some(format!("Nullable<{}>", sql_type))
+    } else {
+        Some(sql_type.to_string())
+    }
+}
+
+/// 记录无法推导 diesel::sql_types 的字段, 严格模式下报错, 否则 schema.rs 中使用 Text
+pub fn check_sql_types(tables: &[Table], template_type: TemplateType, field_types: &FieldTypes) {
+    for table in tables {
+        for field in table.fields.iter() {
+            if sql_type(&template_type, field).is_none() {
+                field_types.record_unmapped(
+                    &format!("{}.{}", table.table_name, field.column_name),
+                    &field.database_field_type,
+                );
+            }
+        }
+    }
+}
+
+fn ident_filter(naming: Naming, value: &Value) -> tera::Result<Value> {
+    let name = tera::try_get_value!("diesel_ident", "value", String, value);
+    Ok(Value::String(naming.field_name(&snake_identifier(&name))))
+}
+
+/// 数据库字段名对应的模型字段名, 即 schema.rs 中的字段名
+fn column_filter(value: &Value, args: &HashMap<String, Value>) -> tera::Result<Value> {
+    let column_name = tera::try_get_value!("diesel_column", "value", String, value);
+    let table = args
+        .get("table")
+        .ok_or_else(|| tera::Error::msg("diesel_column expects a `table` argument"))?;
+    let table: Table = tera::try_get_value!("diesel_column", "table", Table, table);
+    table
+        .fields
+        .into_iter()
+        .find(|v| v.column_name == column_name)
+        .map(|v| Value::String(v.field_name))
+        .ok_or_else(|| tera::Error::msg(format!("unknown column: {}", column_name)))
+}
+
+fn type_filter(template_type: TemplateType, value: &Value) -> tera::Result<Value> {
+    let field = tera::try_get_value!("diesel_type", "value", Field, value);
+    let sql_type = sql_type(&template_type, &field).unwrap_or_else(|| match field.is_null {
+        1 => "Nullable<Text>".to_string(),
+        _ => "Text".to_string(),
+    });
+    Ok(Value::String(sql_type))
+}
+
+/// 注册 diesel_ident / diesel_column / diesel_type 过滤器, diesel_ident 按 naming 转义保留字
+pub fn register_filters(tera: &mut Tera, template_type: TemplateType, naming: Naming) {
+    tera.register_filter(
+        "diesel_ident",
+        move |value: &Value, _: &HashMap<String, Value>| ident_filter(naming, value),
+    );
+    tera.register_filter("diesel_column", column_filter);
+    tera.register_filter(
+        "diesel_type",
+        move |value: &Value, _: &HashMap<String, Value>| type_filter(template_type, value),
+    );
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct Joinable {
+    child: String,
+    parent: String,
+    column: String,
+}
+
+/// joinable! 只支持单字段外键引用父表的单字段主键, 两表间有多个外键时无法确定关联字段, 跳过
+fn joinables(tables: &[Table]) -> Vec<Joinable> {
+    let mut list = vec![];
+    for table in tables {
+        let mut parents: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for key in table.foreign_keys.iter() {
+            parents
+                .entry(&key.referenced_table)
+                .or_default()
+                .push(&key.column);
+        }
+        for (parent, columns) in parents {
+            let Some(parent_table) = tables.iter().find(|v| v.table_name == parent) else {
+                continue;
+            };
+            if parent == table.table_name
+                || columns.len() != 1
+                || parent_table.primary_key.len() != 1
+            {
+                continue;
+            }
+            let Some(field) = table.fields.iter().find(|v| v.column_name == columns[0]) else {
+                continue;
+            };
+            list.push(Joinable {
+                child: table.module_name.to_owned(),
+                parent: parent_table.module_name.to_owned(),
+                column: field.field_name.to_owned(),
+            });
+        }
+    }
+    list
+}
+
+/// 渲染 schema.rs
+pub fn render_schema(
+    tables: &[Table],
+    template_type: TemplateType,
+    naming: Naming,
+) -> tera::Result<String> {
+    let mut tera = Tera::default();
+    register_filters(&mut tera, template_type, naming);
+    tera.add_raw_template("schema", SCHEMA_TEMPLATE)?;
+    let mut context = Context::new();
+    context.insert("tables", tables);
+    context.insert("joinables", &joinables(tables));
+    tera.render("schema", &context)
+}
+
+#[cfg(all(test, feature = "mysql", feature = "postgres", feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use crate::keywords::Escape;
+    use crate::table::{Field, ForeignKey};
+    use crate::template::render::Renderer;
+    use crate::template::template_type::Target;
+
+    fn make_field(column_name: &str, database_field_type: &str, field_type: &str) -> Field {
+        Field {
+            column_name: column_name.to_string(),
+            field_name: column_name.to_string(),
+            database_field_type: database_field_type.to_string(),
+            field_type: field_type.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_sql_type() {
+        let sql_type = |template_type, database_field_type, field_type| {
+            sql_type(
+                &template_type,
+                &make_field("a", database_field_type, field_type),
+            )
+        };
+        assert_eq!(
+            sql_type(TemplateType::Mysql, "bigint(20) unsigned", "u64").unwrap(),
+            "Unsigned<BigInt>"
+        );
+        assert_eq!(
+            sql_type(TemplateType::Mysql, "datetime", "chrono::NaiveDateTime").unwrap(),
+            "Datetime"
+        );
+        assert_eq!(
+            sql_type(
+                TemplateType::Postgres,
+                "timestamptz",
+                "chrono::DateTime<chrono::Utc>"
+            )
+            .unwrap(),
+            "Timestamptz"
+        );
+        assert_eq!(
+            sql_type(TemplateType::Postgres, "jsonb", "serde_json::Value").unwrap(),
+            "Jsonb"
+        );
+        assert_eq!(
+            sql_type(TemplateType::Sqlite, "INTEGER", "i64").unwrap(),
+            "BigInt"
+        );
+        // 按类型映射后的 Rust 类型推导, 例如 tinyint(1) 映射为 bool, numeric 映射为 f64
+        assert_eq!(
+            sql_type(TemplateType::Mysql, "tinyint(1)", "bool").unwrap(),
+            "Bool"
+        );
+        assert_eq!(
+            sql_type(TemplateType::Postgres, "numeric", "f64").unwrap(),
+            "Double"
+        );
+        let mut field = make_field("a", "varchar(255)", "String");
+        field.is_null = 1;
+        assert_eq!(
+            super::sql_type(&TemplateType::Mysql, &field).as_deref(),
+            Some("Nullable<Text>")
+        );
+        // 无法对应的类型不再使用 Text
+        assert_eq!(sql_type(TemplateType::Mysql, "year", "Year"), None);
+        assert_eq!(sql_type(TemplateType::Postgres, "serial", "u32"), None);
+
+        let table = Table {
+            table_name: "t".to_string(),
+            fields: vec![
+                make_field("id", "bigint", "i64"),
+                make_field("y", "year", "Year"),
+            ],
+            ..Default::default()
+        };
+        let field_types = FieldTypes::default();
+        check_sql_types(&[table], TemplateType::Mysql, &field_types);
+        assert_eq!(field_types.unmapped(), ["t.y: year"]);
+    }
+
+    #[test]
+    fn test_render_schema() {
+        let users = Table {
+            table_name: "users".to_string(),
+            fields: vec![make_field("id", "bigint", "i64")],
+            primary_key: vec!["id".to_string()],
+            ..Default::default()
+        };
+        let orders = Table {
+            table_name: "orders".to_string(),
+            fields: vec![
+                make_field("id", "bigint", "i64"),
+                make_field("userId", "bigint", "i64"),
+                make_field("type", "text", "String"),
+            ],
+            foreign_keys: vec![ForeignKey {
+                column: "userId".to_string(),
+                referenced_table: "users".to_string(),
+                referenced_column: "id".to_string(),
+            }],
+            ..Default::default()
+        };
+        let report = Table {
+            table_name: "2024_report".to_string(),
+            fields: vec![
+                make_field("user-id", "bigint", "i64"),
+                make_field("user id", "bigint", "i64"),
+            ],
+            primary_key: vec!["user id".to_string()],
+            ..Default::default()
+        };
+        let renderer = Renderer::new(TemplateType::Mysql).target(Target::Diesel);
+        let mut tables = Table::with_primary_key(&[users, orders, report, Table::default()]);
+        assert_eq!(tables.len(), 3);
+        assert_eq!(tables[1].primary_key, vec!["id".to_string()]);
+        renderer.name_tables(&mut tables);
+        let data = render_schema(&tables, TemplateType::Mysql, renderer.naming()).unwrap();
+        assert!(data.contains("orders (id) {"));
+        assert!(data.contains("\n        userId -> BigInt,"));
+        assert!(data.contains("\n        r#type -> Text,"));
+        assert!(!data.contains("#[sql_name = \"type\"]"));
+        assert!(data.contains("diesel::joinable!(orders -> users (userId));"));
+        assert!(data.contains("diesel::allow_tables_to_appear_in_same_query!("));
+        assert!(data.contains(
+            "#[sql_name = \"2024_report\"]\n    _2024_report (user_id_2) {\n        \
+             #[sql_name = \"user-id\"]\n        user_id -> BigInt,\n        \
+             #[sql_name = \"user id\"]\n        user_id_2 -> BigInt,"
+        ));
+
+        // 后缀转义的字段名与列名不同, 需要 sql_name
+        let renderer = renderer.escape(Escape::Suffix);
+        renderer.name_tables(&mut tables);
+        let data = render_schema(&tables, TemplateType::Mysql, renderer.naming()).unwrap();
+        assert!(data.contains("#[sql_name = \"type\"]\n        type_ -> Text,"));
+    }
+}
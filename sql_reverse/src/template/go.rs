@@ -0,0 +1,27 @@
+pub static GO_TEMPLATE: &str = r#"package {{ package }}
+{% set types = table.fields | map(attribute="field_type") | join(sep=" ") %}
+{%- if "time.Time" in types or "json.RawMessage" in types %}
+import (
+{%- if "json.RawMessage" in types %}
+	"encoding/json"
+{%- endif %}
+{%- if "time.Time" in types %}
+	"time"
+{%- endif %}
+)
+{% endif %}
+// {{ table.struct_name }}TableName is the name of table `{{ table.table_name }}`.
+const {{ table.struct_name }}TableName = "{{ table.table_name | addslashes }}"
+
+// {{ table.struct_name }} {% if table.comment %}{{ table.comment }}{% else %}maps table `{{ table.table_name }}`.{% endif %}
+type {{ table.struct_name }} struct {
+{%- for v in table.fields %}
+	{{ v.field_name }} {% if v.is_null == 1 %}*{% endif %}{{ v.field_type }} `db:"{{ v.column_name }}" json:"{{ v.column_name }}"`{% if v.comment %} // {{ v.comment }}{% endif %}
+{%- endfor %}
+}
+
+// TableName returns the table name, used by gorm.
+func ({{ table.struct_name }}) TableName() string {
+	return {{ table.struct_name }}TableName
+}
+"#;
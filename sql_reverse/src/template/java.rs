@@ -0,0 +1,22 @@
+pub static JAVA_TEMPLATE: &str = r#"package {{ package }};
+
+/**
+ * {% if table.comment %}{{ table.comment }}{% else %}Table {@code {{ table.table_name }}}.{% endif %}
+{%- for v in table.fields %}
+ * @param {{ v.field_name }} {% if v.comment %}{{ v.comment }}{% else %}column {@code {{ v.column_name }}}{% endif %}
+{%- endfor %}
+ */
+public record {{ table.struct_name }}(
+{%- for v in table.fields %}
+        {{ v.field_type }} {{ v.field_name }}{% if loop.last == false %},{% endif %}
+{%- endfor %}
+) {
+    public static final String TABLE_NAME = "{{ table.table_name | addslashes }}";
+
+    public static final java.util.List<String> COLUMNS = java.util.List.of(
+{%- for v in table.fields %}
+            "{{ v.column_name | addslashes }}"{% if loop.last == false %},{% endif %}
+{%- endfor %}
+    );
+}
+"#;
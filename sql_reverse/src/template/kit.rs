@@ -1,34 +1,77 @@
 use crate::error::Result;
 use crate::reverse_impl::common::CustomConfig;
-use regex::Regex;
-use std::collections::BTreeMap;
+use inflector::Inflector;
+use std::collections::HashSet;
 use std::io::{self, Write};
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
+/// 规范数据库表名/字段名: 字母(包括中文等非 ASCII 字母)、数字和 _ 保留,
+/// 空格、连字符、括号等其他字符替换为 _, 数字开头时加 _ 前缀, 全部为符号时使用 unnamed
+pub fn identifier(name: &str) -> String {
+    let ident = name
+        .split(|c: char| !(c.is_alphabetic() || c.is_ascii_digit() || c == '_'))
+        .filter(|v| !v.is_empty())
+        .collect::<Vec<&str>>()
+        .join("_");
+    leading_digit(ident)
+}
+
+fn leading_digit(ident: String) -> String {
+    match ident.chars().next() {
+        None => "unnamed".to_string(),
+        Some(c) if c.is_alphabetic() || c == '_' => ident,
+        Some(_) => format!("_{}", ident),
+    }
+}
+
+/// 规范后转为蛇形命名, 例如: user-profile -> user_profile
+pub fn snake_identifier(name: &str) -> String {
+    leading_digit(identifier(name).to_snake_case())
+}
+
+/// 规范后转为首字母小写驼峰, 例如: user-profile -> userProfile
+pub fn camel_identifier(name: &str) -> String {
+    leading_digit(identifier(name).to_camel_case())
+}
+
+/// 规范后转为首字母大写驼峰, 例如: 2024_report -> _2024Report
+pub fn pascal_identifier(name: &str) -> String {
+    leading_digit(identifier(name).to_pascal_case())
+}
+
+/// 命名规范、类型映射等公共方法
+#[allow(async_fn_in_trait)]
 pub trait Kit {
     /// 字符串首字母大写
     fn first_char_to_uppercase(params: &str) -> String {
-        let mut v: Vec<char> = params.chars().collect();
-        v[0] = v[0].to_uppercase().nth(0).unwrap();
-        v.into_iter().collect()
+        let mut chars = params.chars();
+        match chars.next() {
+            Some(c) => c.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        }
     }
 
-    fn get_field_type(
-        field_type: &str,
-        field_name: &str,
-        field_type_map: &BTreeMap<String, String>,
-    ) -> Result<String> {
-        for (k, v) in field_type_map.iter() {
-            let r = Regex::new(k.trim())?;
-            if r.is_match(field_type) {
-                return Ok(v.to_string());
-            }
+    /// 规范后的名称重复时追加 _2, _3 ... 并提示, 大写开头的驼峰名追加 2, 3 ...,
+    /// seen 记录已使用的名称, original 为数据库中的原始名称
+    fn unique_identifier(name: String, seen: &mut HashSet<String>, original: &str) -> String {
+        let separator = if name.starts_with(|c: char| c.is_uppercase()) {
+            ""
+        } else {
+            "_"
+        };
+        let mut unique = name.clone();
+        let mut n = 1;
+        while !seen.insert(unique.clone()) {
+            n += 1;
+            unique = format!("{}{}{}", name, separator, n);
+        }
+        if unique != name {
+            let _ = Self::write_red(&format!(
+                "{} conflicts with another name after normalization as {}, renamed to {}",
+                original, name, unique
+            ));
         }
-        Self::write_red(&format!(
-            "field_name:{}, {} field type does not match, default type <String> will be used",
-            field_name, field_type
-        ))?;
-        Ok(String::from("String"))
+        unique
     }
 
     fn write_red(text: &str) -> io::Result<()> {
@@ -38,8 +81,52 @@ pub trait Kit {
     }
 
     async fn load(filename: &str) -> Result<CustomConfig> {
-        let s = tokio::fs::read_to_string(filename).await?;
-        let config: CustomConfig = serde_yaml::from_str(&s)?;
-        Ok(config)
+        CustomConfig::load(filename).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct K;
+    impl Kit for K {}
+
+    #[test]
+    fn test_identifier() {
+        assert_eq!(identifier("user-profile"), "user_profile");
+        assert_eq!(identifier("first name"), "first_name");
+        assert_eq!(identifier("price (usd)"), "price_usd");
+        assert_eq!(identifier("用户名"), "用户名");
+        assert_eq!(identifier("2024_report"), "_2024_report");
+        assert_eq!(identifier("--"), "unnamed");
+        assert_eq!(pascal_identifier("2024_report"), "_2024Report");
+        assert_eq!(camel_identifier("user-profile"), "userProfile");
+        assert_eq!(snake_identifier("UserProfile"), "user_profile");
+    }
+
+    #[test]
+    fn test_unique_identifier() {
+        let mut seen = HashSet::new();
+        assert_eq!(
+            K::unique_identifier("user_id".to_string(), &mut seen, "user_id"),
+            "user_id"
+        );
+        assert_eq!(
+            K::unique_identifier("user_id".to_string(), &mut seen, "user-id"),
+            "user_id_2"
+        );
+        assert_eq!(
+            K::unique_identifier("user_id".to_string(), &mut seen, "user id"),
+            "user_id_3"
+        );
+        assert_eq!(
+            K::unique_identifier("UserId".to_string(), &mut seen, "user_id"),
+            "UserId"
+        );
+        assert_eq!(
+            K::unique_identifier("UserId".to_string(), &mut seen, "user-id"),
+            "UserId2"
+        );
     }
 }
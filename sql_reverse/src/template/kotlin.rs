@@ -0,0 +1,24 @@
+pub static KOTLIN_TEMPLATE: &str = r#"package {{ package }}
+
+{% if table.comment -%}
+/** {{ table.comment }} */
+{% endif -%}
+data class {{ table.struct_name }}(
+{%- for v in table.fields %}
+    {%- if v.comment %}
+    /** {{ v.comment }} */
+    {%- endif %}
+    val {{ v.field_name }}: {{ v.field_type }}{% if v.is_null == 1 %}?{% endif %},
+{%- endfor %}
+) {
+    companion object {
+        const val TABLE_NAME = "{{ table.table_name | addslashes }}"
+
+        val COLUMNS = listOf(
+{%- for v in table.fields %}
+            "{{ v.column_name | addslashes }}",
+{%- endfor %}
+        )
+    }
+}
+"#;
@@ -0,0 +1,396 @@
+use crate::error::{Error, Result};
+use crate::keywords::module_file_name;
+use crate::template::kit::{camel_identifier, identifier, pascal_identifier, snake_identifier};
+use crate::template::template_type::Target;
+use regex::Regex;
+use std::str::FromStr;
+
+/// 生成代码的目标语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Lang {
+    Rust,
+    TypeScript,
+    Go,
+    Python,
+    Java,
+    Kotlin,
+}
+
+/// 字段类型映射表先把数据库类型映射为 Rust 类型, 其他语言再按类型种类转换, key: 正则, value: 类型种类
+const RUST_KINDS: &[(&str, &str)] = &[
+    (
+        r"^(bool|i8|i16|i32|i64|i128|u8|u16|u32|u64|u128|f32|f64|String)$",
+        "$1",
+    ),
+    (r"^Vec<u8>$", "bytes"),
+    (r"^(chrono::)?(NaiveDateTime|DateTime<.+>)$", "datetime"),
+    (r"^(chrono::|time::)?(NaiveDate|Date)$", "date"),
+    (r"^(chrono::|time::)?(NaiveTime|Time)$", "time"),
+    (r"^Year$", "i16"),
+    (r"Decimal$", "decimal"),
+    (r"^serde_json::Value$", "json"),
+    (r"Uuid$", "uuid"),
+];
+
+const TYPESCRIPT_TYPES: &[(&str, &str)] = &[
+    ("bool", "boolean"),
+    ("i8", "number"),
+    ("i16", "number"),
+    ("i32", "number"),
+    ("i64", "number"),
+    ("i128", "bigint"),
+    ("u8", "number"),
+    ("u16", "number"),
+    ("u32", "number"),
+    ("u64", "number"),
+    ("u128", "bigint"),
+    ("f32", "number"),
+    ("f64", "number"),
+    ("String", "string"),
+    ("bytes", "Uint8Array"),
+    ("datetime", "Date"),
+    ("date", "Date"),
+    ("time", "string"),
+    ("decimal", "string"),
+    ("json", "unknown"),
+    ("uuid", "string"),
+];
+
+const GO_TYPES: &[(&str, &str)] = &[
+    ("bool", "bool"),
+    ("i8", "int8"),
+    ("i16", "int16"),
+    ("i32", "int32"),
+    ("i64", "int64"),
+    ("i128", "string"),
+    ("u8", "uint8"),
+    ("u16", "uint16"),
+    ("u32", "uint32"),
+    ("u64", "uint64"),
+    ("u128", "string"),
+    ("f32", "float32"),
+    ("f64", "float64"),
+    ("String", "string"),
+    ("bytes", "[]byte"),
+    ("datetime", "time.Time"),
+    ("date", "time.Time"),
+    ("time", "string"),
+    ("decimal", "string"),
+    ("json", "json.RawMessage"),
+    ("uuid", "string"),
+];
+
+const PYTHON_TYPES: &[(&str, &str)] = &[
+    ("bool", "bool"),
+    ("i8", "int"),
+    ("i16", "int"),
+    ("i32", "int"),
+    ("i64", "int"),
+    ("i128", "int"),
+    ("u8", "int"),
+    ("u16", "int"),
+    ("u32", "int"),
+    ("u64", "int"),
+    ("u128", "int"),
+    ("f32", "float"),
+    ("f64", "float"),
+    ("String", "str"),
+    ("bytes", "bytes"),
+    ("datetime", "datetime.datetime"),
+    ("date", "datetime.date"),
+    ("time", "datetime.time"),
+    ("decimal", "decimal.Decimal"),
+    ("json", "Any"),
+    ("uuid", "uuid.UUID"),
+];
+
+const JAVA_TYPES: &[(&str, &str)] = &[
+    ("bool", "Boolean"),
+    ("i8", "Byte"),
+    ("i16", "Short"),
+    ("i32", "Integer"),
+    ("i64", "Long"),
+    ("i128", "java.math.BigInteger"),
+    ("u8", "Short"),
+    ("u16", "Integer"),
+    ("u32", "Long"),
+    ("u64", "java.math.BigInteger"),
+    ("u128", "java.math.BigInteger"),
+    ("f32", "Float"),
+    ("f64", "Double"),
+    ("String", "String"),
+    ("bytes", "byte[]"),
+    ("datetime", "java.time.LocalDateTime"),
+    ("date", "java.time.LocalDate"),
+    ("time", "java.time.LocalTime"),
+    ("decimal", "java.math.BigDecimal"),
+    ("json", "Object"),
+    ("uuid", "java.util.UUID"),
+];
+
+const KOTLIN_TYPES: &[(&str, &str)] = &[
+    ("bool", "Boolean"),
+    ("i8", "Byte"),
+    ("i16", "Short"),
+    ("i32", "Int"),
+    ("i64", "Long"),
+    ("i128", "java.math.BigInteger"),
+    ("u8", "Short"),
+    ("u16", "Int"),
+    ("u32", "Long"),
+    ("u64", "java.math.BigInteger"),
+    ("u128", "java.math.BigInteger"),
+    ("f32", "Float"),
+    ("f64", "Double"),
+    ("String", "String"),
+    ("bytes", "ByteArray"),
+    ("datetime", "java.time.LocalDateTime"),
+    ("date", "java.time.LocalDate"),
+    ("time", "java.time.LocalTime"),
+    ("decimal", "java.math.BigDecimal"),
+    ("json", "Any"),
+    ("uuid", "java.util.UUID"),
+];
+
+impl Lang {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Lang::Rust => "rust",
+            Lang::TypeScript => "typescript",
+            Lang::Go => "go",
+            Lang::Python => "python",
+            Lang::Java => "java",
+            Lang::Kotlin => "kotlin",
+        }
+    }
+
+    /// 生成文件的默认后缀, 同时决定使用哪种语言的关键字转义
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Lang::Rust => "rs",
+            Lang::TypeScript => "ts",
+            Lang::Go => "go",
+            Lang::Python => "py",
+            Lang::Java => "java",
+            Lang::Kotlin => "kt",
+        }
+    }
+
+    /// 未指定 -n 时使用的模板名, --target 只对 Rust 生效
+    pub fn template_name(&self, target: Target, database: &str) -> Result<String> {
+        match (self, target) {
+            (Lang::Rust, target) => Ok(target.template_name(database)),
+            (_, Target::Sqlx) => Ok(format!("{}.tera", self.name())),
+            (_, target) => Err(Error::UnsupportedTarget(target, self.name().to_string())),
+        }
+    }
+
+    /// 按语言命名习惯生成字段名, Rust 只替换无效字符, 保持数据库字段名的大小写
+    pub fn field_name(&self, column_name: &str) -> String {
+        match self {
+            Lang::Rust => identifier(column_name),
+            Lang::Python => snake_identifier(column_name),
+            // Go 只导出大写字母开头的字段
+            Lang::Go => {
+                let name = pascal_identifier(column_name);
+                if name.starts_with(|c: char| c.is_uppercase()) {
+                    name
+                } else {
+                    format!("X{}", name.trim_start_matches('_'))
+                }
+            }
+            Lang::TypeScript | Lang::Java | Lang::Kotlin => camel_identifier(column_name),
+        }
+    }
+
+    /// 按语言命名习惯生成结构体名, Go 只导出大写字母开头的类型, 例如: 2024_report -> T2024Report
+    pub fn struct_name(&self, table_name: &str) -> String {
+        let name = pascal_identifier(table_name);
+        match self {
+            Lang::Go if !name.starts_with(|c: char| c.is_uppercase()) => {
+                format!("T{}", name.trim_start_matches('_'))
+            }
+            _ => name,
+        }
+    }
+
+    /// 生成文件名(不含后缀), Java/Kotlin 要求文件名与类名一致,
+    /// go 命令忽略 _ 开头的文件, Go 使用 t 前缀, 例如: 2024_report -> t2024_report
+    pub fn file_name(&self, table_name: &str, struct_name: &str) -> String {
+        match self {
+            Lang::Java | Lang::Kotlin => struct_name.to_string(),
+            Lang::Python => snake_identifier(table_name),
+            Lang::Go => {
+                let name = identifier(table_name);
+                match name.strip_prefix('_') {
+                    Some(v) => format!("t{}", v.trim_start_matches('_')),
+                    None => name,
+                }
+            }
+            _ => identifier(table_name),
+        }
+    }
+
+    /// 汇总导出所有模块的索引文件名(不含后缀), Go/Java/Kotlin 按目录组织包, 不需要索引文件
+    pub fn index_file(&self) -> Option<&'static str> {
+        match self {
+            Lang::Rust => Some("mod"),
+            Lang::TypeScript => Some("index"),
+            Lang::Python => Some("__init__"),
+            Lang::Go | Lang::Java | Lang::Kotlin => None,
+        }
+    }
+
+    /// 索引文件中导出单个模块的语句
+    pub fn index_line(&self, file_name: &str, struct_name: &str) -> String {
+        match self {
+            Lang::TypeScript => format!("export * from './{}';\n", file_name),
+            Lang::Python => format!("from .{} import {}\n", file_name, struct_name),
+            // 非 ASCII 的模块名需要用 path 指定文件, 否则报 E0754
+            _ if !file_name.is_ascii() => format!(
+                "#[path = \"{}.rs\"]\npub mod {};\n",
+                module_file_name(file_name),
+                file_name
+            ),
+            _ => format!("pub mod {};\n", file_name),
+        }
+    }
+
+    /// 把字段映射表得到的 Rust 类型转换为目标语言类型, 无法识别的类型原样保留
+    pub fn field_type(&self, rust_type: &str) -> String {
+        let types = match self {
+            Lang::Rust => return rust_type.to_string(),
+            Lang::TypeScript => TYPESCRIPT_TYPES,
+            Lang::Go => GO_TYPES,
+            Lang::Python => PYTHON_TYPES,
+            Lang::Java => JAVA_TYPES,
+            Lang::Kotlin => KOTLIN_TYPES,
+        };
+        let rust_type = rust_type.trim();
+        if let Some(inner) = generic_inner(rust_type, "Option") {
+            return self.field_type(inner);
+        }
+        if let Some(inner) = generic_inner(rust_type, "Vec").filter(|v| *v != "u8") {
+            let inner = self.field_type(inner);
+            return match self {
+                Lang::TypeScript => format!("{}[]", inner),
+                Lang::Go => format!("[]{}", inner),
+                Lang::Python => format!("list[{}]", inner),
+                Lang::Java => format!("java.util.List<{}>", inner),
+                _ => format!("List<{}>", inner),
+            };
+        }
+        let kind = RUST_KINDS.iter().find_map(|(k, v)| {
+            let r = Regex::new(k).ok()?;
+            let captures = r.captures(rust_type)?;
+            let mut kind = String::new();
+            captures.expand(v, &mut kind);
+            Some(kind)
+        });
+        kind.and_then(|kind| types.iter().find(|(k, _)| *k == kind))
+            .map(|(_, v)| v.to_string())
+            .unwrap_or_else(|| rust_type.to_string())
+    }
+}
+
+fn generic_inner<'a>(rust_type: &'a str, name: &str) -> Option<&'a str> {
+    rust_type
+        .strip_prefix(name)?
+        .strip_prefix('<')?
+        .strip_suffix('>')
+        .map(|v| v.trim())
+}
+
+impl FromStr for Lang {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "rust" | "rs" => Ok(Lang::Rust),
+            "typescript" | "ts" => Ok(Lang::TypeScript),
+            "go" => Ok(Lang::Go),
+            "python" | "py" => Ok(Lang::Python),
+            "java" => Ok(Lang::Java),
+            "kotlin" | "kt" => Ok(Lang::Kotlin),
+            _ => Err(format!(
+                "unknown lang: {}, expected rust, typescript, go, python, java or kotlin",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_type() {
+        assert_eq!(Lang::Go.field_type("i64"), "int64");
+        assert_eq!(Lang::TypeScript.field_type("i64"), "number");
+        assert_eq!(Lang::Java.field_type("i64"), "Long");
+        assert_eq!(Lang::Kotlin.field_type("i32"), "Int");
+        assert_eq!(
+            Lang::Python.field_type("chrono::NaiveDateTime"),
+            "datetime.datetime"
+        );
+        assert_eq!(
+            Lang::Java.field_type("chrono::NaiveDate"),
+            "java.time.LocalDate"
+        );
+        assert_eq!(Lang::Go.field_type("sqlx::types::Decimal"), "string");
+        assert_eq!(Lang::Go.field_type("Vec<u8>"), "[]byte");
+        assert_eq!(Lang::TypeScript.field_type("Vec<String>"), "string[]");
+        assert_eq!(Lang::Kotlin.field_type("Option<Vec<i64>>"), "List<Long>");
+        assert_eq!(Lang::Python.field_type("Year"), "int");
+        // 自定义映射中无法识别的类型原样保留
+        assert_eq!(Lang::Go.field_type("sql.NullString"), "sql.NullString");
+        assert_eq!(Lang::Rust.field_type("Vec<u8>"), "Vec<u8>");
+    }
+
+    #[test]
+    fn test_naming() {
+        assert_eq!(Lang::Go.field_name("user_id"), "UserId");
+        assert_eq!(Lang::TypeScript.field_name("user_id"), "userId");
+        assert_eq!(Lang::Python.field_name("userId"), "user_id");
+        assert_eq!(Lang::Rust.field_name("userId"), "userId");
+        assert_eq!(Lang::Go.field_name("用户名"), "X用户名");
+        assert_eq!(Lang::Rust.field_name("2fa-enabled"), "_2fa_enabled");
+        assert_eq!(Lang::Java.file_name("user_info", "UserInfo"), "UserInfo");
+        assert_eq!(
+            Lang::Rust.file_name("user-profile", "UserProfile"),
+            "user_profile"
+        );
+        assert_eq!(Lang::Python.file_name("user_info", "UserInfo"), "user_info");
+        assert_eq!(
+            Lang::Python.index_line("user_info", "UserInfo"),
+            "from .user_info import UserInfo\n"
+        );
+        assert_eq!(
+            Lang::Rust.index_line("用户", "用户"),
+            "#[path = \"用户.rs\"]\npub mod 用户;\n"
+        );
+        assert_eq!(Lang::Go.struct_name("2024_report"), "T2024Report");
+        assert_eq!(Lang::Go.struct_name("用户"), "T用户");
+        assert_eq!(Lang::Rust.struct_name("2024_report"), "_2024Report");
+        assert_eq!(
+            Lang::Go.file_name("2024_report", "T2024Report"),
+            "t2024_report"
+        );
+        assert_eq!(Lang::Go.file_name("user-info", "UserInfo"), "user_info");
+    }
+
+    #[test]
+    fn test_template_name() {
+        assert_eq!(
+            Lang::Rust.template_name(Target::Diesel, "mysql").unwrap(),
+            "diesel.tera"
+        );
+        assert_eq!(
+            Lang::Go.template_name(Target::Sqlx, "mysql").unwrap(),
+            "go.tera"
+        );
+        assert!(Lang::Go.template_name(Target::SeaOrm, "mysql").is_err());
+    }
+}
@@ -1,8 +1,21 @@
+#[cfg(feature = "clickhouse")]
 mod clickhouse;
+pub mod diesel;
+mod go;
+mod java;
 pub mod kit;
+mod kotlin;
+pub mod lang;
+#[cfg(feature = "mysql")]
 mod mysql;
+#[cfg(feature = "postgres")]
 mod postgres;
+mod python;
 pub mod render;
+pub mod seaorm;
+#[cfg(feature = "sqlite")]
 mod sqlite;
+#[cfg(feature = "tdengine")]
 mod tdengine;
 pub mod template_type;
+mod typescript;
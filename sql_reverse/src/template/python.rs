@@ -0,0 +1,22 @@
+pub static PYTHON_TEMPLATE: &str = r#"import datetime
+import decimal
+import uuid
+from dataclasses import dataclass
+from typing import Any, ClassVar, Optional
+
+
+@dataclass
+class {{ table.struct_name }}:
+{%- if table.comment %}
+    """{{ table.comment }}"""
+{% endif %}
+    TABLE_NAME: ClassVar[str] = "{{ table.table_name | addslashes }}"
+    COLUMNS: ClassVar[tuple[str, ...]] = (
+{%- for v in table.fields %}
+        "{{ v.column_name | addslashes }}",
+{%- endfor %}
+    )
+{% for v in table.fields %}
+    {{ v.field_name }}: {% if v.is_null == 1 %}Optional[{{ v.field_type }}]{% else %}{{ v.field_type }}{% endif %}{% if v.comment %}  # {{ v.comment }}{% endif %}
+{%- endfor %}
+"#;
@@ -10,18 +10,52 @@ example: [
 ]
 */
 // *************************************************************************************************"#;
+/// Python 不支持 // 和 /* */ 注释, 使用 # 注释和字符串包裹自定义配置
+const PY_FLAG: &str = "# ***************************************以下是自定义代码区域******************************************";
+const PY_FLAG2: &str = r#"
+"""
+/*
+example: [
+    {"skip_fields": ["updated_at", "created_at"], "filename": "table_name1"},
+    {"contain_fields": ["updated_at", "created_at"], "filename": "table_name2"}
+]
+*/
+"""
+# *************************************************************************************************"#;
 
 use crate::error::Result;
+use crate::keywords::{Escape, Naming, module_file_name};
+use crate::reverse_impl::common::{ConnectionConfig, CustomConfig};
+use crate::reverse_impl::gen_struct::DynGenStruct;
+use crate::reverse_impl::type_map::{FieldTypes, TypeMap};
 use crate::table::Table;
+#[cfg(feature = "clickhouse")]
 use crate::template::clickhouse::CLICKHOUSE_TEMPLATE;
+use crate::template::diesel;
+use crate::template::diesel::DIESEL_TEMPLATE;
+use crate::template::go::GO_TEMPLATE;
+use crate::template::java::JAVA_TEMPLATE;
+use crate::template::kit::{Kit, camel_identifier, identifier, pascal_identifier};
+use crate::template::kotlin::KOTLIN_TEMPLATE;
+use crate::template::lang::Lang;
+#[cfg(feature = "mysql")]
 use crate::template::mysql::MYSQL_TEMPLATE;
+#[cfg(feature = "postgres")]
 use crate::template::postgres::POSTGRES_TEMPLATE;
+use crate::template::python::PYTHON_TEMPLATE;
+use crate::template::seaorm;
+use crate::template::seaorm::SEAORM_TEMPLATE;
+#[cfg(feature = "sqlite")]
 use crate::template::sqlite::SQLITE_TEMPLATE;
+#[cfg(feature = "tdengine")]
 use crate::template::tdengine::TDENGINE_TEMPLATE;
-use crate::template::template_type::{TEMPLATE_TYPE, TemplateType};
+use crate::template::template_type::{Target, TemplateType};
+use crate::template::typescript::TYPESCRIPT_TEMPLATE;
+use inflector::Inflector;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
-use tera::{Context, Tera};
+use tera::{Context, Tera, Value};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct FilterFields {
@@ -48,10 +82,290 @@ async fn filter_fields(table: &Table, params: Vec<FilterFields>) -> Result<Vec<(
     Ok(list)
 }
 
-pub trait Render {
-    async fn check_download_tera(template_path: &str, template_name: &str) -> Result<()> {
-        let file = format!("{}{}", template_path.replace("*", ""), template_name);
-        let _ = tokio::fs::create_dir(template_path.replace("*", "")).await;
+/// 自定义代码区域的分隔标记和默认示例
+fn flags(lang: Lang) -> (&'static str, &'static str) {
+    match lang {
+        Lang::Python => (PY_FLAG, PY_FLAG2),
+        _ => (FLAG, FLAG2),
+    }
+}
+
+/// ident 过滤器: 把字段名规范为标识符, 用于拼接方法名, 例如 select_by_{{ index | ident }}
+fn ident_filter(value: &Value, _: &HashMap<String, Value>) -> tera::Result<Value> {
+    let name = tera::try_get_value!("ident", "value", String, value);
+    Ok(Value::String(identifier(&name)))
+}
+
+/// inner_type 过滤器: 去掉可空字段类型外层的 Option, 用于查询条件的参数类型, 例如 Option<i32> -> i32
+fn inner_type_filter(value: &Value, _: &HashMap<String, Value>) -> tera::Result<Value> {
+    let field_type = tera::try_get_value!("inner_type", "value", String, value);
+    let inner = field_type
+        .strip_prefix("Option<")
+        .and_then(|v| v.strip_suffix('>'))
+        .unwrap_or(&field_type);
+    Ok(Value::String(inner.to_string()))
+}
+
+async fn write_to_file(filepath: &str, content: &str) -> Result<()> {
+    let filepath = Path::new(&filepath);
+    let mut f = tokio::fs::File::options()
+        .create(true)
+        .truncate(true)
+        .write(true)
+        .open(filepath)
+        .await?;
+    f.write_all(content.as_bytes()).await?;
+    Ok(())
+}
+
+/// 代码渲染配置, 默认使用 Rust + sqlx 和 templates/ 目录下的 <数据库名>.tera 模板, 模板不存在时写入内置模板
+#[derive(Debug, Clone)]
+pub struct Renderer {
+    pub(crate) template_type: TemplateType,
+    pub(crate) lang: Lang,
+    target: Target,
+    suffix: Option<String>,
+    template_path: String,
+    template_name: Option<String>,
+    builtin_template: Option<String>,
+    connection: ConnectionConfig,
+    escape: Escape,
+    type_map: Option<TypeMap>,
+    pub(crate) strict: bool,
+}
+
+impl Renderer {
+    pub fn new(template_type: TemplateType) -> Self {
+        Self {
+            template_type,
+            lang: Lang::Rust,
+            target: Target::Sqlx,
+            suffix: None,
+            template_path: "templates/*".to_string(),
+            template_name: None,
+            builtin_template: None,
+            connection: ConnectionConfig::default(),
+            escape: Escape::default(),
+            type_map: None,
+            strict: false,
+        }
+    }
+
+    pub fn template_type(&self) -> TemplateType {
+        self.template_type
+    }
+
+    /// 生成代码的语言, 默认 Rust
+    pub fn lang(mut self, lang: Lang) -> Self {
+        self.lang = lang;
+        self
+    }
+
+    /// 生成的 Rust 代码使用的库, 默认 sqlx, ClickHouse 和 TDengine 只支持 sqlx
+    pub fn target(mut self, target: Target) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// 生成文件的后缀, 默认使用语言的后缀, 同时决定使用哪种语言的关键字转义
+    pub fn suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.suffix = Some(suffix.into());
+        self
+    }
+
+    /// 模板目录, 例如 templates/*
+    pub fn template_path(mut self, template_path: impl Into<String>) -> Self {
+        self.template_path = template_path.into();
+        self
+    }
+
+    /// 模板名, 默认为 `<数据库名>.tera`, 指定 target 时为 `<target>.tera`, 指定 lang 时为 `<lang>.tera`
+    pub fn template_name(mut self, template_name: impl Into<String>) -> Self {
+        self.template_name = Some(template_name.into());
+        self
+    }
+
+    /// 替换内置模板, 模板目录中没有模板文件时写入该模板, 只对当前 Renderer 生效
+    pub fn builtin_template(mut self, template: impl Into<String>) -> Self {
+        self.builtin_template = Some(template.into());
+        self
+    }
+
+    /// 生成代码 mod.rs 中的连接配置
+    pub fn connection(mut self, connection: ConnectionConfig) -> Self {
+        self.connection = connection;
+        self
+    }
+
+    /// 保留字转义方式
+    pub fn escape(mut self, escape: Escape) -> Self {
+        self.escape = escape;
+        self
+    }
+
+    /// 字段类型映射, 替换数据库的默认类型映射, 例如 [`TypeMap::load`] 读取的 json 文件
+    pub fn type_map(mut self, type_map: TypeMap) -> Self {
+        self.type_map = Some(type_map);
+        self
+    }
+
+    /// 存在未匹配的字段类型时报错, 默认提示后使用 String, 配置文件中的 strict 为 true 时同样报错
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// 使用配置文件中的 connection 和 escape
+    pub fn config(self, config: &CustomConfig) -> Self {
+        self.connection(config.connection.to_owned().unwrap_or_default())
+            .escape(config.escape.unwrap_or_default())
+    }
+
+    /// 按当前配置读取表结构
+    pub async fn tables(&self, db: &dyn DynGenStruct) -> Result<Vec<Table>> {
+        db.run_boxed(self).await
+    }
+
+    /// 按语言和转义方式生成结构体名、模块名和字段名后渲染到 output_dir, 保留已生成文件中的自定义代码区域
+    pub async fn render(&self, tables: &[Table], output_dir: &str) -> Result<()> {
+        let template_name = match &self.template_name {
+            Some(template_name) => template_name.to_owned(),
+            None => self
+                .lang
+                .template_name(self.effective_target(), self.template_type.name())?,
+        };
+        self.check_download_tera(&template_name).await?;
+        let mut tables = tables.to_vec();
+        self.name_tables(&mut tables);
+        self.render_rust(&template_name, output_dir, &tables).await
+    }
+
+    /// 生成文件的后缀决定关键字转义使用的语言
+    pub(crate) fn naming(&self) -> Naming {
+        Naming::new(&self.file_suffix(), self.escape)
+    }
+
+    /// 按语言和转义方式生成结构体名、模块名、字段名和字段类型, 规范、转义后重复的名称追加序号
+    pub(crate) fn name_tables(&self, tables: &mut [Table]) {
+        let lang = self.lang;
+        let naming = self.naming();
+        let mut struct_names = HashSet::new();
+        let mut module_names = HashSet::new();
+        for table in tables.iter_mut() {
+            table.struct_name = Table::unique_identifier(
+                naming.struct_name(&lang.struct_name(&table.table_name)),
+                &mut struct_names,
+                &table.table_name,
+            );
+            table.module_name = Table::unique_identifier(
+                naming.module_name(&lang.file_name(&table.table_name, &table.struct_name)),
+                &mut module_names,
+                &table.table_name,
+            );
+            let mut field_names = HashSet::new();
+            let mut upper_names = HashSet::new();
+            let mut lower_names = HashSet::new();
+            for field in table.fields.iter_mut() {
+                let field_name = lang.field_name(&field.column_name);
+                field.field_name = Table::unique_identifier(
+                    naming.field_name(&field_name),
+                    &mut field_names,
+                    &field.column_name,
+                );
+                // 用作枚举变体, 例如 self 列的 Self 需要转义
+                field.FieldName = Table::unique_identifier(
+                    naming.struct_name(&pascal_identifier(&field.column_name)),
+                    &mut upper_names,
+                    &field.column_name,
+                );
+                field.fieldName = Table::unique_identifier(
+                    naming.field_name(&camel_identifier(&field.column_name)),
+                    &mut lower_names,
+                    &field.column_name,
+                );
+                field.field_type = lang.field_type(&field.field_type);
+                if naming.escape() == Escape::Rename && field.field_name != field_name {
+                    field.rename = Some(field_name);
+                }
+            }
+            // 枚举的成员为变体名, 结构体的成员为字段名
+            for custom_type in table.custom_types.iter_mut() {
+                let mut seen = HashSet::new();
+                for member in custom_type.members.iter_mut() {
+                    let name = match custom_type.repr {
+                        Some(_) => naming.struct_name(&member.name),
+                        None => naming.field_name(&member.name),
+                    };
+                    member.name = Table::unique_identifier(name, &mut seen, &member.original);
+                }
+            }
+        }
+    }
+
+    /// 一次读取表结构使用的类型映射, 未指定时使用数据库的默认类型映射
+    pub(crate) fn field_types(&self) -> FieldTypes {
+        let type_map = self
+            .type_map
+            .to_owned()
+            .unwrap_or_else(|| TypeMap::defaults(self.template_type));
+        FieldTypes::new(type_map)
+    }
+
+    fn file_suffix(&self) -> String {
+        self.suffix
+            .to_owned()
+            .unwrap_or_else(|| self.lang.suffix().to_string())
+    }
+
+    /// ClickHouse 和 TDengine 只支持 sqlx
+    pub(crate) fn effective_target(&self) -> Target {
+        match self.template_type {
+            #[cfg(feature = "clickhouse")]
+            TemplateType::Clickhouse => Target::Sqlx,
+            #[cfg(feature = "tdengine")]
+            TemplateType::Tdengine => Target::Sqlx,
+            #[allow(unreachable_patterns)]
+            _ => self.target,
+        }
+    }
+
+    /// 语言、目标库和数据库对应的内置模板
+    fn default_template(&self) -> &'static str {
+        match (self.lang, self.effective_target()) {
+            (Lang::TypeScript, _) => TYPESCRIPT_TEMPLATE,
+            (Lang::Go, _) => GO_TEMPLATE,
+            (Lang::Python, _) => PYTHON_TEMPLATE,
+            (Lang::Java, _) => JAVA_TEMPLATE,
+            (Lang::Kotlin, _) => KOTLIN_TEMPLATE,
+            (Lang::Rust, Target::Diesel) => DIESEL_TEMPLATE,
+            (Lang::Rust, Target::SeaOrm) => SEAORM_TEMPLATE,
+            (Lang::Rust, Target::Sqlx) => match self.template_type {
+                #[cfg(feature = "mysql")]
+                TemplateType::Mysql => MYSQL_TEMPLATE,
+                #[cfg(feature = "sqlite")]
+                TemplateType::Sqlite => SQLITE_TEMPLATE,
+                #[cfg(feature = "postgres")]
+                TemplateType::Postgres => POSTGRES_TEMPLATE,
+                #[cfg(feature = "clickhouse")]
+                TemplateType::Clickhouse => CLICKHOUSE_TEMPLATE,
+                #[cfg(feature = "tdengine")]
+                TemplateType::Tdengine => TDENGINE_TEMPLATE,
+            },
+        }
+    }
+
+    /// 模板文件不存在时写入的模板, 未替换时使用内置模板
+    fn template_content(&self) -> &str {
+        self.builtin_template
+            .as_deref()
+            .unwrap_or_else(|| self.default_template())
+    }
+
+    /// 模板不存在时写入内置模板
+    async fn check_download_tera(&self, template_name: &str) -> Result<()> {
+        let template_dir = self.template_path.replace("*", "");
+        let file = format!("{}{}", template_dir, template_name);
+        let _ = tokio::fs::create_dir(template_dir).await;
         if !tokio::fs::try_exists(&file).await.unwrap_or_default() {
             let mut fs = tokio::fs::File::options()
                 .create(true)
@@ -59,130 +373,147 @@ pub trait Render {
                 .write(true)
                 .open(&file)
                 .await?;
-            match *TEMPLATE_TYPE.read().unwrap() {
-                TemplateType::Mysql => {
-                    let data = MYSQL_TEMPLATE.read().unwrap().as_bytes();
-                    fs.write_all(data).await?;
-                }
-                TemplateType::Sqlite => {
-                    let data = SQLITE_TEMPLATE.read().unwrap().as_bytes();
-                    fs.write_all(data).await?;
-                }
-                TemplateType::Postgres => {
-                    let data = POSTGRES_TEMPLATE.read().unwrap().as_bytes();
-                    fs.write_all(data).await?;
-                }
-                TemplateType::Clickhouse => {
-                    let data = CLICKHOUSE_TEMPLATE.read().unwrap().as_bytes();
-                    fs.write_all(data).await?;
-                }
-                TemplateType::Tdengine => {
-                    let data = TDENGINE_TEMPLATE.read().unwrap().as_bytes();
-                    fs.write_all(data).await?;
-                }
-            }
+            fs.write_all(self.template_content().as_bytes()).await?;
         }
         Ok(())
     }
+
+    /// 注册模板使用的过滤器, diesel / sea-orm 模板还需要对应的过滤器
+    fn register_filters(&self, tera: &mut Tera, tables: &[Table]) {
+        tera.register_filter("ident", ident_filter);
+        tera.register_filter("inner_type", inner_type_filter);
+        match self.effective_target() {
+            Target::Sqlx => {}
+            Target::Diesel => diesel::register_filters(tera, self.template_type, self.naming()),
+            Target::SeaOrm => seaorm::register(tera, tables, self.template_type, self.naming()),
+        }
+    }
+
     async fn render_rust(
-        template_path: &str,
+        &self,
         template_name: &str,
-        suffix: &str,
         output_dir: &str,
-        tables: &Vec<Table>,
+        tables: &[Table],
     ) -> Result<()> {
         let _ = create_dir(output_dir).await;
-        let tera = Tera::new(template_path)?;
+        let mut tera = Tera::new(&self.template_path)?;
+        let suffix = &self.file_suffix();
+        let orm_tables;
+        let tables = match self.effective_target() {
+            Target::Sqlx => tables,
+            Target::Diesel => {
+                orm_tables = Table::with_primary_key(tables);
+                let schema_path = format!("{}/schema.{}", output_dir, suffix);
+                let schema = diesel::render_schema(&orm_tables, self.template_type, self.naming())?;
+                write_to_file(&schema_path, &schema).await?;
+                &orm_tables[..]
+            }
+            Target::SeaOrm => {
+                orm_tables = Table::with_primary_key(tables);
+                let prelude_path = format!("{}/prelude.{}", output_dir, suffix);
+                write_to_file(&prelude_path, &seaorm::render_prelude(&orm_tables)).await?;
+                &orm_tables[..]
+            }
+        };
+        self.register_filters(&mut tera, tables);
+        let lang = self.lang;
+        let (flag, flag2) = flags(lang);
         let mut mods = vec![];
         for table in tables {
-            mods.push(format!("pub mod {};\n", table.table_name));
-            let (mut struct_str, mut custom, filepath) = Self::render_table(
-                &tera,
-                table,
-                template_name,
-                suffix,
-                output_dir,
-                &table.table_name,
-            )
-            .await?;
+            let filename = module_file_name(&table.module_name);
+            mods.push(lang.index_line(&table.module_name, &table.struct_name));
+            let (mut struct_str, mut custom, filepath) = self
+                .render_table(&tera, table, template_name, output_dir, filename)
+                .await?;
             if !custom.is_empty() {
                 let data: Vec<&str> = custom.split("*/").collect();
                 let data = data.first().unwrap_or(&"").to_string();
                 let data = data.replace("/*", "");
-                let data = data.trim();
+                let data = data.trim().trim_matches('"').trim();
                 let params: Vec<FilterFields> = serde_json::from_str(data).unwrap_or(vec![]);
                 let filters = filter_fields(table, params).await?;
                 for filter in filters.iter() {
-                    mods.push(format!("pub mod {};\n", filter.1));
-                    let (mut struct_str, custom, filepath) = Self::render_table(
-                        &tera,
-                        &filter.0,
-                        template_name,
-                        suffix,
-                        output_dir,
-                        &filter.1,
-                    )
-                    .await?;
-                    struct_str = struct_str + "\n" + FLAG + custom.as_str();
-                    Self::write_to_file(&filepath, &struct_str).await?;
+                    mods.push(lang.index_line(&filter.1, &filter.0.struct_name));
+                    let (mut struct_str, custom, filepath) = self
+                        .render_table(&tera, &filter.0, template_name, output_dir, &filter.1)
+                        .await?;
+                    struct_str = struct_str + "\n" + flag + custom.as_str();
+                    write_to_file(&filepath, &struct_str).await?;
                 }
             }
             if custom.trim() == "" {
-                custom = FLAG2.to_owned();
+                custom = flag2.to_owned();
             }
-            struct_str = struct_str + "\n" + FLAG + custom.as_str();
-            Self::write_to_file(&filepath, &struct_str).await?;
+            struct_str = struct_str + "\n" + flag + custom.as_str();
+            write_to_file(&filepath, &struct_str).await?;
         }
 
-        if suffix == "rs" {
-            let mod_path = format!("{}/mod.{}", output_dir, suffix);
-            Self::append_to_file(mods, &mod_path).await?;
+        // Rust 只有后缀为 rs 时才生成 mod.rs
+        if let Some(index) = lang
+            .index_file()
+            .filter(|_| lang != Lang::Rust || suffix == "rs")
+        {
+            let index_path = format!("{}/{}.{}", output_dir, index, suffix);
+            self.append_to_file(mods, &index_path).await?;
         }
 
         Ok(())
     }
 
+    /// 渲染一张表使用的模板变量
+    fn context(&self, table: &Table, output_dir: &str) -> Context {
+        let mut context = Context::new();
+        context.insert("table", table);
+        context.insert("key_columns", &table.key_columns());
+        context.insert("update_fields", &table.update_fields());
+        let mut connection = self.connection.to_owned();
+        connection.mode = self.template_type.connection_mode(&connection);
+        context.insert("connection", &connection);
+        // Go/Java/Kotlin 的包名, 取输出目录名
+        let package = Path::new(output_dir)
+            .file_name()
+            .map(|v| v.to_string_lossy().to_snake_case())
+            .unwrap_or_default();
+        context.insert("package", &package);
+        if self.effective_target() == Target::Diesel {
+            context.insert("backend", self.template_type.diesel_backend());
+        }
+        context
+    }
+
     async fn render_table(
+        &self,
         tera: &Tera,
         table: &Table,
         template_name: &str,
-        suffix: &str,
         output_dir: &str,
         filename: &str,
     ) -> Result<(String, String, String)> {
-        let mut context = Context::new();
-        context.insert("table", table);
+        let context = self.context(table, output_dir);
         let struct_str = tera.render(template_name, &context)?;
-        let filepath = format!("{}/{}.{}", output_dir, filename, suffix);
+        let filepath = format!("{}/{}.{}", output_dir, filename, self.file_suffix());
         let content = tokio::fs::read_to_string(&filepath)
             .await
             .unwrap_or_default();
-        let vv: Vec<&str> = content.split(FLAG).collect();
+        let vv: Vec<&str> = content.split(flags(self.lang).0).collect();
         let custom = vv.get(1).unwrap_or(&"").to_string();
         Ok((struct_str, custom, filepath))
     }
 
-    async fn write_to_file(filepath: &str, content: &str) -> Result<()> {
-        let filepath = Path::new(&filepath);
-        let mut f = tokio::fs::File::options()
-            .create(true)
-            .truncate(true)
-            .write(true)
-            .open(filepath)
-            .await?;
-        f.write_all(content.as_bytes()).await?;
-        Ok(())
-    }
-
-    async fn append_to_file(mods: Vec<String>, filepath: &str) -> Result<()> {
+    async fn append_to_file(&self, mods: Vec<String>, filepath: &str) -> Result<()> {
         if let Ok(mut fs) = tokio::fs::File::options()
             .create_new(true)
             .write(true)
             .open(filepath)
             .await
         {
-            fs.write_all(TEMPLATE_TYPE.read().unwrap().to_string().as_bytes())
-                .await?;
+            let header = match (self.lang, self.effective_target()) {
+                (Lang::Rust, Target::Sqlx) => self.template_type.preamble(&self.connection)?,
+                (Lang::Rust, Target::Diesel) => diesel::MOD_TEMPLATE.to_string(),
+                (Lang::Rust, Target::SeaOrm) => seaorm::MOD_TEMPLATE.to_string(),
+                _ => String::new(),
+            };
+            fs.write_all(header.as_bytes()).await?;
         }
         let file_content = tokio::fs::read_to_string(filepath)
             .await
@@ -200,3 +531,483 @@ pub trait Render {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reverse_impl::common::SoftDeleteConfig;
+    use crate::table::{Field, SoftDelete};
+    use regex::Regex;
+
+    /// 各测试共用的表: id 主键, name 唯一索引, type 普通索引, self/type 为保留字, remark 可空, is_deleted 为软删除字段
+    fn users() -> Table {
+        let mut remark = Field::new("remark", "varchar(255)", "String");
+        remark.is_null = 1;
+        let mut table = Table::new(
+            "users",
+            vec![
+                Field::new("id", "bigint", "i64"),
+                Field::new("name", "varchar(64)", "String"),
+                Field::new("self", "varchar(64)", "String"),
+                Field::new("type", "int", "i32"),
+                remark,
+                Field::new("is_deleted", "tinyint", "i8"),
+            ],
+        );
+        table.primary_key = vec!["id".to_string()];
+        table.unique_key = vec![vec!["id".to_string()], vec!["name".to_string()]];
+        table.index_key = vec![vec!["type".to_string()]];
+        table
+    }
+
+    /// 只有主键和唯一索引字段的表
+    fn keys_only() -> Table {
+        let mut table = users();
+        table.fields.truncate(2);
+        table.index_key.clear();
+        table
+    }
+
+    /// 已启用的 sqlx 数据库, ClickHouse 和 TDengine 的模板单独测试
+    fn sql_types() -> Vec<TemplateType> {
+        vec![
+            #[cfg(feature = "mysql")]
+            TemplateType::Mysql,
+            #[cfg(feature = "postgres")]
+            TemplateType::Postgres,
+            #[cfg(feature = "sqlite")]
+            TemplateType::Sqlite,
+        ]
+    }
+
+    /// 按 renderer 的命名规则处理表并用内置模板渲染
+    fn render_builtin(renderer: &Renderer, table: Table) -> String {
+        let mut tables = [table];
+        renderer.name_tables(&mut tables);
+        let mut tera = Tera::default();
+        renderer.register_filters(&mut tera, &tables);
+        tera.add_raw_template("builtin", renderer.template_content())
+            .unwrap();
+        tera.render("builtin", &renderer.context(&tables[0], "models"))
+            .unwrap()
+    }
+
+    /// 从 start 开始到下一个顶层的 } 为止, 例如结构体定义或 impl 块
+    fn section<'a>(data: &'a str, start: &str) -> &'a str {
+        let data = &data[data
+            .find(start)
+            .unwrap_or_else(|| panic!("{} not found", start))..];
+        &data[..data.find("\n}\n").unwrap_or(data.len())]
+    }
+
+    /// 方法 name 的定义, 到方法结束为止
+    fn method<'a>(data: &'a str, name: &str) -> &'a str {
+        let start = format!("fn {}(", name);
+        let data = &data[data
+            .find(&start)
+            .unwrap_or_else(|| panic!("{} not found", start))..];
+        &data[..data.find("\n    }\n").unwrap_or(data.len())]
+    }
+
+    /// 方法 name 中 format! 拼接的 SQL
+    fn method_sql<'a>(data: &'a str, name: &str) -> &'a str {
+        let sql = Regex::new(r#"format!\("((?:[^"\\]|\\.)*)""#).unwrap();
+        sql.captures(method(data, name))
+            .and_then(|v| v.get(1))
+            .unwrap_or_else(|| panic!("no sql in {}", name))
+            .as_str()
+    }
+
+    /// SQL 中加引号的字段名, 按出现顺序, 连续重复的只保留一个
+    fn columns(sql: &str) -> Vec<&str> {
+        let quoted = Regex::new(r#"`([^`]+)`|\\"([^"\\]+)\\""#).unwrap();
+        let mut list: Vec<&str> = quoted
+            .captures_iter(sql)
+            .map(|v| v.get(1).or(v.get(2)).unwrap().as_str())
+            .collect();
+        list.dedup();
+        list
+    }
+
+    /// 匹配 pattern 第一个分组的所有结果
+    fn captures<'a>(data: &'a str, pattern: &str) -> Vec<&'a str> {
+        Regex::new(pattern)
+            .unwrap()
+            .captures_iter(data)
+            .map(|v| v.get(1).unwrap().as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_update() {
+        for template_type in sql_types() {
+            let name = template_type.name();
+            let mut table = users();
+            table.soft_delete =
+                SoftDelete::new(&SoftDeleteConfig::default(), &table.fields, &template_type);
+            let data = render_builtin(&Renderer::new(template_type), table);
+            // 主键、唯一索引和软删除字段不会被修改, 按 SET 的顺序绑定, 最后绑定条件字段
+            let updatable = ["self", "type", "remark"];
+            for (method_name, key) in [("update_by_id_with", "id"), ("update_by_name_with", "name")]
+            {
+                let sql = method_sql(&data, method_name);
+                let set = &sql[..sql.find(" WHERE ").unwrap()];
+                assert_eq!(columns(set), updatable, "{} {}", name, method_name);
+                assert_eq!(
+                    captures(method(&data, method_name), r"\.bind\(&self\.([\w#]+)\)"),
+                    ["self_", "r#type", "remark", key],
+                    "{} {}",
+                    name,
+                    method_name
+                );
+            }
+
+            // 部分更新结构体只包含可以修改的字段, 只更新 Some 的字段
+            let update = section(&data, "pub struct UsersUpdate {");
+            assert_eq!(
+                captures(update, r"pub ([\w#]+):"),
+                ["self_", "r#type", "remark"],
+                "{}",
+                name
+            );
+            let update = section(&data, "impl UsersUpdate {");
+            let sets: Vec<&str> = update
+                .lines()
+                .filter(|v| v.contains("sets.push"))
+                .flat_map(columns)
+                .collect();
+            assert_eq!(sets, updatable.repeat(2), "{}", name);
+            assert!(update.contains("if sets.is_empty() {\n            return Ok(0);\n        }"));
+
+            // 没有可以修改的字段时不生成
+            let data = render_builtin(&Renderer::new(template_type), keys_only());
+            assert!(!data.contains("fn update_by_"), "{}", name);
+            assert!(!data.contains("UsersUpdate"), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_insert_many_and_upsert() {
+        for template_type in sql_types() {
+            let name = template_type.name();
+            let data = render_builtin(&Renderer::new(template_type), users());
+            // 每批的绑定参数不超过数据库上限: SQLite 32766, MySQL/PostgreSQL 65535
+            let limit = if name == "sqlite" { 32766 } else { 65535 };
+            assert!(
+                method(&data, "insert_many_with").contains(&format!("rows.chunks({} / 6)", limit)),
+                "{}",
+                name
+            );
+
+            // 冲突时只更新主键和唯一索引以外的字段, MySQL 按任意唯一键冲突, PostgreSQL/SQLite 每个唯一键一个方法
+            let methods: &[&str] = match name {
+                "mysql" => &["upsert_with"],
+                _ => &["upsert_by_id_with", "upsert_by_name_with"],
+            };
+            for method_name in methods {
+                let sql = method_sql(&data, method_name);
+                let update = &sql[sql.find(" UPDATE ").unwrap()..];
+                assert_eq!(
+                    columns(update),
+                    ["self", "type", "remark", "is_deleted"],
+                    "{} {}",
+                    name,
+                    method_name
+                );
+            }
+
+            // 只有主键和唯一索引字段时 MySQL 不生成 upsert, PostgreSQL/SQLite 冲突时什么也不做
+            let data = render_builtin(&Renderer::new(template_type), keys_only());
+            match name {
+                "mysql" => assert!(!data.contains("fn upsert")),
+                _ => assert!(method_sql(&data, "upsert_by_id_with").ends_with("DO NOTHING")),
+            }
+        }
+
+        // MySQL 8.0.20 起 VALUES() 已废弃, 使用行别名引用插入的值
+        #[cfg(feature = "mysql")]
+        {
+            let data = render_builtin(&Renderer::new(TemplateType::Mysql), users());
+            let sql = method_sql(&data, "upsert_with");
+            assert!(sql.contains(") AS new ON DUPLICATE KEY UPDATE `self` = new.`self`, "));
+            assert!(!sql.contains("VALUES(`"));
+        }
+    }
+
+    #[test]
+    fn test_with_executor() {
+        use crate::reverse_impl::common::ConnectionMode;
+
+        let method = r"pub async fn ([\w#]+)";
+        for template_type in sql_types() {
+            let name = template_type.name();
+            let pool = format!("{}_POOL", name.to_uppercase());
+            // 每个方法都有接收 executor 的 _with 版本, 不带 _with 的方法使用全局连接池调用它
+            let data = render_builtin(&Renderer::new(template_type), users());
+            assert!(data.contains(&format!("use super::{};", pool)), "{}", name);
+            let methods = captures(&data, method);
+            assert!(methods.contains(&"insert_many"), "{}", name);
+            assert!(methods.contains(&"select_page_by_type_with"), "{}", name);
+            for v in methods.iter().filter(|v| !v.ends_with("_with")) {
+                assert!(
+                    data.contains(&format!("{}_with(&*{}", v, pool)),
+                    "{} {}",
+                    name,
+                    v
+                );
+            }
+
+            // mode = none 时只生成 _with 方法
+            let connection = ConnectionConfig {
+                mode: ConnectionMode::None,
+                ..Default::default()
+            };
+            let renderer = Renderer::new(template_type).connection(connection);
+            let data = render_builtin(&renderer, users());
+            assert!(!data.contains(&pool), "{}", name);
+            let with: Vec<&str> = methods
+                .iter()
+                .filter(|v| v.ends_with("_with"))
+                .copied()
+                .collect();
+            assert_eq!(captures(&data, method), with, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_page_order_by() {
+        let template_types = [
+            #[cfg(feature = "clickhouse")]
+            TemplateType::Clickhouse,
+        ];
+        for template_type in sql_types().into_iter().chain(template_types) {
+            let name = template_type.name();
+            let renderer = Renderer::new(template_type);
+            // 没有普通索引时不生成分页方法, 也不导入 Page
+            let mut table = users();
+            table.index_key.clear();
+            let data = render_builtin(&renderer, table);
+            assert!(data.contains("use super::Result;"), "{}", name);
+            assert!(!data.contains("Page"), "{}", name);
+
+            // 分页按索引字段和主键排序, 结果稳定; ClickHouse 的 select_page_by 调用 select_many_by
+            let data = render_builtin(&renderer, users());
+            assert!(data.contains("use super::{Page, Result};"), "{}", name);
+            let method_name = match name {
+                "clickhouse" => "select_many_by_type_by_page",
+                _ => "select_many_by_type_by_page_with",
+            };
+            let sql = method_sql(&data, method_name);
+            let order_by = &sql[sql.find(" ORDER BY ").unwrap()..sql.find(" LIMIT ").unwrap()];
+            assert_eq!(columns(order_by), ["type", "id"], "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_quoting() {
+        let string = Regex::new(r#""(?:[^"\\]|\\.)*""#).unwrap();
+        let template_types = [
+            #[cfg(feature = "clickhouse")]
+            TemplateType::Clickhouse,
+            #[cfg(feature = "tdengine")]
+            TemplateType::Tdengine,
+        ];
+        for template_type in sql_types().into_iter().chain(template_types) {
+            let name = template_type.name();
+            let mut table = users();
+            table.table_name = "order".to_string();
+            let data = render_builtin(&Renderer::new(template_type), table.clone());
+            // 表名和字段名按数据库加引号, PostgreSQL/SQLite 使用双引号, 其他使用反引号
+            let q = |v: &str| match name {
+                "postgres" | "sqlite" => format!("\\\"{}\\\"", v),
+                _ => format!("`{}`", v),
+            };
+            assert!(
+                data.contains(&format!("pub const TABLE_NAME: &str = \"{}\";", q("order"))),
+                "{}",
+                name
+            );
+            let fields: Vec<String> = table.fields.iter().map(|v| q(&v.column_name)).collect();
+            assert!(
+                data.contains(&format!(
+                    "pub const FIELDS: &str = \"{}\";",
+                    fields.join(",")
+                )),
+                "{}",
+                name
+            );
+            assert!(data.contains(&format!("Self::Type => \"{}\",", q("type"))));
+            // Rust 的原始标识符不能出现在 SQL 中
+            for v in string.find_iter(&data) {
+                assert!(!v.as_str().contains("r#"), "{} {}", name, v.as_str());
+            }
+            // 占位符按数据库区分, TDengine 的 SQL 直接拼接时间范围, 不使用占位符
+            let placeholder = match name {
+                "postgres" => "$1",
+                "tdengine" => continue,
+                _ => "?",
+            };
+            assert!(
+                data.contains(&format!(
+                    "FROM {{TABLE_NAME}} WHERE  {} = {} ",
+                    q("id"),
+                    placeholder
+                )),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_query_builder() {
+        let value = inner_type_filter(&Value::from("Option<Vec<u8>>"), &HashMap::new()).unwrap();
+        assert_eq!(value, "Vec<u8>");
+
+        for template_type in sql_types() {
+            let name = template_type.name();
+            let data = render_builtin(&Renderer::new(template_type), users());
+            let query = section(&data, "impl UsersQuery {");
+            let methods = captures(query, r"pub fn (\w+)\(");
+            // 方法名去掉保留字转义的后缀和原始标识符前缀, 不会生成 self__eq、type__gt
+            assert!(
+                methods.iter().all(|v| !v.contains("__")),
+                "{} {:?}",
+                name,
+                methods
+            );
+            for v in [
+                "self_eq",
+                "type_gt",
+                "type_in",
+                "order_by_self_asc",
+                "order_by_type_desc",
+            ] {
+                assert!(methods.contains(&v), "{} {}", name, v);
+            }
+            // 字符串支持 LIKE, 数字支持范围比较, 只有可空字段支持 IS NULL
+            assert!(methods.contains(&"name_like"), "{}", name);
+            assert!(!methods.contains(&"name_gt"), "{}", name);
+            assert!(methods.contains(&"remark_is_null"), "{}", name);
+            assert!(!methods.contains(&"name_is_null"), "{}", name);
+            // 可空字段的比较参数使用内部类型
+            assert!(query.contains("pub fn remark_eq(self, value: impl Into<String>) -> Self {"));
+            assert!(
+                query.contains(
+                    "pub fn type_in(self, values: impl IntoIterator<Item = i32>) -> Self {"
+                )
+            );
+            assert!(method(query, "count_with").contains(
+                "let sql = format!(\"SELECT COUNT(*) FROM {TABLE_NAME}{}\", self.where_sql());"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_column_enum() {
+        for template_type in sql_types() {
+            let name = template_type.name();
+            let data = render_builtin(&Renderer::new(template_type), users());
+            // self 列的变体不能是 Self
+            assert_eq!(
+                captures(section(&data, "pub enum UsersColumn {"), r"\n    (\w+),"),
+                ["Id", "Name", "Self_", "Type", "Remark", "IsDeleted"],
+                "{}",
+                name
+            );
+            assert!(data.contains("pub const ALL: [Self; 6] = ["), "{}", name);
+            assert!(data.contains("pub const COLUMN_SELF_: &str = \"self\";"));
+            assert!(data.contains("Self::Self_ => COLUMN_SELF_,"));
+            assert!(data.contains("Self::Type => COLUMN_TYPE,"));
+        }
+    }
+
+    #[cfg(feature = "mysql")]
+    #[test]
+    fn test_diesel_model() {
+        let renderer = Renderer::new(TemplateType::Mysql).target(Target::Diesel);
+        let data = render_builtin(&renderer, users());
+        assert!(data.contains("use super::schema::users;"));
+        assert!(data.contains("#[diesel(primary_key(id))]"));
+        assert_eq!(
+            captures(section(&data, "pub struct Users {"), r"pub ([\w#]+):"),
+            ["id", "name", "self_", "r#type", "remark", "is_deleted"]
+        );
+
+        // 规范后重复的字段名追加序号, 数字开头的表名加 _ 前缀
+        let mut table = users();
+        table.table_name = "2024_report".to_string();
+        table.fields[0].column_name = "user-id".to_string();
+        table.fields[1].column_name = "user id".to_string();
+        table.primary_key = vec!["user id".to_string()];
+        table.unique_key.clear();
+        let data = render_builtin(&renderer, table);
+        assert!(data.contains("use super::schema::_2024_report;"));
+        assert!(data.contains("#[diesel(table_name = _2024_report)]"));
+        assert!(data.contains("#[diesel(primary_key(user_id_2))]"));
+        assert!(data.contains("    pub user_id: i64,\n    pub user_id_2: String,"));
+    }
+
+    #[cfg(feature = "mysql")]
+    #[test]
+    fn test_seaorm_entity() {
+        let renderer = Renderer::new(TemplateType::Mysql).target(Target::SeaOrm);
+        let mut table = users();
+        table.table_name = "2024_report".to_string();
+        table.fields[0].column_name = "user-id".to_string();
+        table.fields[1].column_name = "user id".to_string();
+        table.fields[3].column_name = "userId".to_string();
+        table.primary_key = vec!["user id".to_string()];
+        table.unique_key.clear();
+        let data = render_builtin(&renderer, table);
+        assert!(data.contains("#[sea_orm(table_name = \"2024_report\")]"));
+        // 字段名与列名不同时指定 column_name, 变体名与字段名推导的不同时指定 enum_name
+        let model = section(&data, "pub struct Model {");
+        let attrs = |field: &str| {
+            let end = model.find(&format!("\n    pub {}:", field)).unwrap();
+            model[..end].rsplit('\n').next().unwrap()
+        };
+        assert_eq!(
+            attrs("user_id"),
+            "    #[sea_orm(column_name = \"user-id\")]"
+        );
+        assert!(
+            attrs("user_id_2")
+                .contains("primary_key, auto_increment = false, column_name = \"user id\"")
+        );
+        assert!(attrs("self_").contains("column_name = \"self\", enum_name = \"Self_\""));
+        assert_eq!(
+            attrs("userId"),
+            "    #[sea_orm(column_name = \"userId\", enum_name = \"UserId3\")]"
+        );
+        assert!(!attrs("is_deleted").contains("sea_orm"));
+    }
+
+    #[cfg(feature = "tdengine")]
+    #[test]
+    fn test_tdengine_soft_delete() {
+        let template_type = TemplateType::Tdengine;
+        let mut table = Table::new(
+            "meters",
+            vec![
+                Field::new("ts", "TIMESTAMP", "chrono::NaiveDateTime"),
+                Field::new("current", "FLOAT", "f32"),
+                Field::new("is_deleted", "TINYINT", "i8"),
+            ],
+        );
+        table.soft_delete =
+            SoftDelete::new(&SoftDeleteConfig::default(), &table.fields, &template_type);
+        assert!(table.soft_delete.is_none());
+        // TDengine 没有 UPDATE, 按时间范围删除始终使用 DELETE, 查询不过滤 is_deleted
+        let data = render_builtin(&Renderer::new(template_type), table);
+        assert!(!data.contains("UPDATE"));
+        assert!(
+            method_sql(&data, "delete_by_ts_range")
+                .starts_with("DELETE FROM {TABLE_NAME} WHERE `ts` >= {} AND `ts` < {}")
+        );
+        assert_eq!(
+            method_sql(&data, "select_all"),
+            "SELECT {FIELDS} FROM {TABLE_NAME}"
+        );
+    }
+}
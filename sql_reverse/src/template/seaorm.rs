@@ -0,0 +1,403 @@
+use crate::keywords::Naming;
+use crate::table::{Field, Table};
+use crate::template::kit::snake_identifier;
+use crate::template::template_type::TemplateType;
+use inflector::Inflector;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use tera::{Tera, Value};
+
+pub static SEAORM_TEMPLATE: &str = r#"
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+{% set relations = seaorm_relations(table=table.table_name) %}
+{% if table.comment -%}
+/// {{ table.comment }}
+{% endif -%}
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "{{ table.table_name | addslashes }}")]
+pub struct Model {
+{%- for v in table.fields %}
+    {%- if v.comment %}
+    /// {{ v.comment }}
+    {%- endif %}
+    {%- set attrs = v | seaorm_attrs(table=table) %}
+    {%- if attrs %}
+    #[sea_orm({{ attrs }})]
+    {%- endif %}
+    pub {{ v.field_name }}: {% if v.is_null == 1 %}Option<{{ v.field_type }}>{% else %}{{ v.field_type }}{% endif %},
+{%- endfor %}
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+{%- for v in relations %}
+    {%- if v.kind == "belongs_to" %}
+    #[sea_orm(
+        belongs_to = "super::{{ v.module }}::Entity",
+        from = "Column::{{ v.from }}",
+        to = "super::{{ v.module }}::Column::{{ v.to }}"
+    )]
+    {%- else %}
+    #[sea_orm(has_many = "super::{{ v.module }}::Entity")]
+    {%- endif %}
+    {{ v.name }},
+{%- endfor %}
+}
+{% for v in relations %}{% if v.related %}
+impl Related<super::{{ v.module }}::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::{{ v.name }}.def()
+    }
+}
+{% endif %}{% endfor %}
+impl ActiveModelBehavior for ActiveModel {}
+"#;
+
+/// mod.rs 头部
+pub static MOD_TEMPLATE: &str = r#"
+pub type Result<T> = std::result::Result<T, sea_orm::DbErr>;
+pub mod prelude;
+"#;
+
+#[cfg(feature = "mysql")]
+const MYSQL_COLUMN_TYPES: &[(&str, &str)] = &[
+    (r"^varchar\((\d+)\)$", "String(StringLen::N($1))"),
+    (r"^char\((\d+)\)$", "Char(Some($1))"),
+    (r"^(tinytext|text|mediumtext|longtext)$", "Text"),
+    (
+        r"^(decimal|numeric)\((\d+),(\d+)\)$",
+        "Decimal(Some(($2, $3)))",
+    ),
+    (r"^float", "Float"),
+    (r"^double", "Double"),
+    (r"^datetime", "DateTime"),
+    (r"^timestamp", "Timestamp"),
+    (r"^date$", "Date"),
+    (r"^time$", "Time"),
+    (r"^year", "Year"),
+    (r"^json$", "Json"),
+    (r"^varbinary\((\d+)\)$", "VarBinary(StringLen::N($1))"),
+    (r"^binary\((\d+)\)$", "Binary($1)"),
+    (r"blob$", "Blob"),
+];
+
+#[cfg(feature = "postgres")]
+const POSTGRES_COLUMN_TYPES: &[(&str, &str)] = &[
+    (r"^character varying\((\d+)\)$", "String(StringLen::N($1))"),
+    (r"^character varying$", "String(StringLen::None)"),
+    (r"^character\((\d+)\)$", "Char(Some($1))"),
+    (r"^text$", "Text"),
+    (r"^numeric\((\d+),(\d+)\)$", "Decimal(Some(($1, $2)))"),
+    (r"^numeric$", "Decimal(None)"),
+    (r"^real$", "Float"),
+    (r"^double precision$", "Double"),
+    (
+        r"^timestamp(\(\d+\))? with time zone$",
+        "TimestampWithTimeZone",
+    ),
+    (r"^timestamp", "DateTime"),
+    (r"^date$", "Date"),
+    (r"^time", "Time"),
+    (r"^jsonb$", "JsonBinary"),
+    (r"^json$", "Json"),
+    (r"^bytea$", "VarBinary(StringLen::None)"),
+    (r"^uuid$", "Uuid"),
+];
+
+#[cfg(feature = "sqlite")]
+const SQLITE_COLUMN_TYPES: &[(&str, &str)] = &[
+    (r"(?i)^varchar\((\d+)\)$", "String(StringLen::N($1))"),
+    (r"(?i)^text$", "Text"),
+    (r"(?i)^(real|double|float)$", "Double"),
+    (r"(?i)^(timestamp|datetime)$", "DateTime"),
+    (r"(?i)^date$", "Date"),
+    (r"(?i)^blob$", "Blob"),
+];
+
+/// 按数据库字段类型推导 sea_orm column_type, 未匹配时由字段类型推导, 返回 None
+pub fn column_type(template_type: &TemplateType, database_field_type: &str) -> Option<String> {
+    let types: &[(&str, &str)] = match template_type {
+        #[cfg(feature = "mysql")]
+        TemplateType::Mysql => MYSQL_COLUMN_TYPES,
+        #[cfg(feature = "postgres")]
+        TemplateType::Postgres => POSTGRES_COLUMN_TYPES,
+        #[cfg(feature = "sqlite")]
+        TemplateType::Sqlite => SQLITE_COLUMN_TYPES,
+        #[cfg(feature = "clickhouse")]
+        TemplateType::Clickhouse => &[],
+        #[cfg(feature = "tdengine")]
+        TemplateType::Tdengine => &[],
+    };
+    let database_field_type = database_field_type.trim();
+    types.iter().find_map(|(k, v)| {
+        let captures = Regex::new(k).ok()?.captures(database_field_type)?;
+        let mut data = String::new();
+        captures.expand(v, &mut data);
+        Some(data)
+    })
+}
+
+/// 字段的 #[sea_orm(..)] 属性
+fn attributes(template_type: &TemplateType, table: &Table, field: &Field) -> String {
+    let mut attrs = vec![];
+    if table.primary_key.contains(&field.column_name) {
+        attrs.push("primary_key".to_string());
+        let integer = field.field_type.starts_with('i') || field.field_type.starts_with('u');
+        if table.primary_key.len() > 1 || !integer {
+            attrs.push("auto_increment = false".to_string());
+        }
+    }
+    // sea-orm 按字段名推导列名和 Column 变体名, 与数据库名称或 FieldName 不同时显式指定
+    let name = field.field_name.trim_start_matches("r#");
+    if field.column_name != name || name != name.to_snake_case() {
+        attrs.push(format!("column_name = {:?}", field.column_name));
+    }
+    if field.FieldName != name.to_pascal_case() {
+        attrs.push(format!("enum_name = {:?}", field.FieldName));
+    }
+    if let Some(column_type) = column_type(template_type, &field.database_field_type) {
+        attrs.push(format!("column_type = {:?}", column_type));
+    }
+    if field.is_null == 1 {
+        attrs.push("nullable".to_string());
+    }
+    let unique = table
+        .unique_key
+        .iter()
+        .any(|keys| keys.len() == 1 && keys[0] == field.column_name);
+    if unique && table.primary_key != [field.column_name.to_owned()] {
+        attrs.push("unique".to_string());
+    }
+    attrs.join(", ")
+}
+
+fn ident_filter(naming: Naming, value: &Value) -> tera::Result<Value> {
+    let name = tera::try_get_value!("seaorm_ident", "value", String, value);
+    Ok(Value::String(naming.field_name(&snake_identifier(&name))))
+}
+
+fn attrs_filter(
+    template_type: TemplateType,
+    value: &Value,
+    args: &HashMap<String, Value>,
+) -> tera::Result<Value> {
+    let field: Field = tera::try_get_value!("seaorm_attrs", "value", Field, value);
+    let table = args
+        .get("table")
+        .ok_or_else(|| tera::Error::msg("seaorm_attrs expects a `table` argument"))?;
+    let table: Table = tera::try_get_value!("seaorm_attrs", "table", Table, table);
+    Ok(Value::String(attributes(&template_type, &table, &field)))
+}
+
+/// 实体间的关联
+#[derive(Serialize, Debug, PartialEq)]
+struct Relation {
+    /// Relation 枚举的变体名
+    name: String,
+    /// belongs_to 或 has_many
+    kind: &'static str,
+    /// 关联表的模块名
+    module: String,
+    /// 本表字段, 仅 belongs_to
+    from: String,
+    /// 关联表字段, 仅 belongs_to
+    to: String,
+    /// 是否生成 impl Related, 两表间有多个关联时无法确定, 不生成
+    related: bool,
+}
+
+/// 字段对应的 Column 变体名, 即 name_tables 生成的 FieldName
+fn column_variant(table: &Table, column_name: &str) -> Option<String> {
+    table
+        .fields
+        .iter()
+        .find(|v| v.column_name == column_name)
+        .map(|v| v.FieldName.to_owned())
+}
+
+/// 按外键生成每张表的 belongs_to/has_many, 只处理本次生成的表之间的外键
+fn relations(tables: &[Table]) -> BTreeMap<String, Vec<Relation>> {
+    let tables_by_name: HashMap<&str, &Table> =
+        tables.iter().map(|v| (v.table_name.as_str(), v)).collect();
+    let mut map: BTreeMap<String, Vec<Relation>> = BTreeMap::new();
+    let mut has_many = vec![];
+    for table in tables {
+        let mut parents: BTreeMap<&str, Vec<_>> = BTreeMap::new();
+        for key in table.foreign_keys.iter() {
+            if tables_by_name.contains_key(key.referenced_table.as_str()) {
+                parents.entry(&key.referenced_table).or_default().push(key);
+            }
+        }
+        let list = map.entry(table.table_name.to_owned()).or_default();
+        for (parent, keys) in parents {
+            let parent_table = tables_by_name[parent];
+            let self_ref = parent == table.table_name;
+            let single = keys.len() == 1 && !self_ref;
+            for key in keys.iter() {
+                let (Some(from), Some(to)) = (
+                    column_variant(table, &key.column),
+                    column_variant(parent_table, &key.referenced_column),
+                ) else {
+                    continue;
+                };
+                let mut name = if self_ref {
+                    "SelfRef".to_string()
+                } else {
+                    parent_table.struct_name.to_owned()
+                };
+                if !single {
+                    name.push_str(&from);
+                }
+                list.push(Relation {
+                    name,
+                    kind: "belongs_to",
+                    module: parent_table.module_name.to_owned(),
+                    from,
+                    to,
+                    related: single,
+                });
+            }
+            if single {
+                has_many.push((parent, table));
+            }
+        }
+    }
+    for (parent, child) in has_many {
+        let list = map.entry(parent.to_string()).or_default();
+        // 两表互相引用时已有 belongs_to, 不再生成重复的 Related
+        if list.iter().any(|v| v.module == child.module_name) {
+            continue;
+        }
+        list.push(Relation {
+            name: child.struct_name.to_owned(),
+            kind: "has_many",
+            module: child.module_name.to_owned(),
+            from: String::new(),
+            to: String::new(),
+            related: true,
+        });
+    }
+    map
+}
+
+/// 注册 seaorm_ident / seaorm_attrs 过滤器和 seaorm_relations 函数, seaorm_ident 按 naming 转义保留字
+pub fn register(tera: &mut Tera, tables: &[Table], template_type: TemplateType, naming: Naming) {
+    tera.register_filter(
+        "seaorm_ident",
+        move |value: &Value, _: &HashMap<String, Value>| ident_filter(naming, value),
+    );
+    tera.register_filter(
+        "seaorm_attrs",
+        move |value: &Value, args: &HashMap<String, Value>| {
+            attrs_filter(template_type, value, args)
+        },
+    );
+    let relations = relations(tables);
+    tera.register_function(
+        "seaorm_relations",
+        move |args: &HashMap<String, Value>| -> tera::Result<Value> {
+            let table_name = args
+                .get("table")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| tera::Error::msg("seaorm_relations expects a `table` argument"))?;
+            let list = relations
+                .get(table_name)
+                .map(|v| v.as_slice())
+                .unwrap_or(&[]);
+            Ok(tera::to_value(list)?)
+        },
+    );
+}
+
+/// 渲染 prelude.rs
+pub fn render_prelude(tables: &[Table]) -> String {
+    let mut data = String::from("// @generated by sql_reverse, do not edit.\n");
+    for table in tables {
+        data.push_str(&format!(
+            "pub use super::{}::Entity as {};\n",
+            table.module_name, table.struct_name
+        ));
+    }
+    data
+}
+
+#[cfg(all(test, feature = "mysql"))]
+mod tests {
+    use super::*;
+    use crate::table::ForeignKey;
+    use crate::template::{render::Renderer, template_type::Target};
+
+    #[test]
+    #[cfg(all(feature = "postgres", feature = "sqlite"))]
+    fn test_column_type() {
+        assert_eq!(
+            column_type(&TemplateType::Mysql, "varchar(64)").as_deref(),
+            Some("String(StringLen::N(64))")
+        );
+        assert_eq!(
+            column_type(&TemplateType::Postgres, "numeric(10,2)").as_deref(),
+            Some("Decimal(Some((10, 2)))")
+        );
+        assert_eq!(
+            column_type(&TemplateType::Postgres, "timestamp without time zone").as_deref(),
+            Some("DateTime")
+        );
+        assert_eq!(column_type(&TemplateType::Postgres, "bigint"), None);
+    }
+
+    #[test]
+    fn test_relations() {
+        let id = Field {
+            column_name: "id".to_string(),
+            ..Default::default()
+        };
+        let users = Table {
+            table_name: "users".to_string(),
+            fields: vec![id.clone()],
+            ..Default::default()
+        };
+        let orders = Table {
+            table_name: "orders".to_string(),
+            fields: vec![
+                id,
+                Field {
+                    column_name: "userId".to_string(),
+                    ..Default::default()
+                },
+            ],
+            foreign_keys: vec![
+                ForeignKey {
+                    column: "userId".to_string(),
+                    referenced_table: "users".to_string(),
+                    referenced_column: "id".to_string(),
+                },
+                ForeignKey {
+                    column: "shop_id".to_string(),
+                    referenced_table: "shops".to_string(),
+                    referenced_column: "id".to_string(),
+                },
+            ],
+            ..Default::default()
+        };
+        let mut tables = [users, orders];
+        Renderer::new(TemplateType::Mysql)
+            .target(Target::SeaOrm)
+            .name_tables(&mut tables);
+        let map = relations(&tables);
+        assert_eq!(
+            map["orders"],
+            vec![Relation {
+                name: "Users".to_string(),
+                kind: "belongs_to",
+                module: "users".to_string(),
+                from: "UserId".to_string(),
+                to: "Id".to_string(),
+                related: true,
+            }]
+        );
+        assert_eq!(map["users"][0].kind, "has_many");
+        assert_eq!(map["users"][0].name, "Orders");
+    }
+}
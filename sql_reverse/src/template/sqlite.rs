@@ -1,16 +1,57 @@
-use std::sync::{LazyLock, RwLock};
-
-pub static SQLITE_TEMPLATE: LazyLock<RwLock<&str>> = LazyLock::new(|| {
-    RwLock::new(
-        r#"
+pub static SQLITE_TEMPLATE: &str = r#"
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
-use super::Result;
+use super::{% if table.index_key %}{Page, Result}{% else %}Result{% endif %};
+{% if connection.mode != "none" -%}
 use super::SQLITE_POOL;
+{% endif -%}
+
+pub const TABLE_NAME: &str = "\"{{table.table_name}}\"";
+
+pub const FIELDS: &str = "{%- for field in table.fields -%}\"{{field.column_name}}\"{%- if loop.last == false -%},{%- endif -%}{%- endfor -%}";
+
+{% for field in table.fields -%}
+pub const COLUMN_{{ field.field_name | replace(from="r#", to="") | upper }}: &str = "{{ field.column_name | addslashes }}";
+{% endfor %}
+/// Columns of `{{ table.table_name }}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum {{ table.struct_name }}Column {
+{%- for field in table.fields %}
+    {{ field.FieldName }},
+{%- endfor %}
+}
 
-pub const TABLE_NAME: &str = "{{table.table_name}}";
+impl {{ table.struct_name }}Column {
+    pub const ALL: [Self; {{ table.fields | length }}] = [
+{%- for field in table.fields %}
+        Self::{{ field.FieldName }},
+{%- endfor %}
+    ];
+
+    /// Raw column name.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+{%- for field in table.fields %}
+            Self::{{ field.FieldName }} => COLUMN_{{ field.field_name | replace(from="r#", to="") | upper }},
+{%- endfor %}
+        }
+    }
 
-pub const FIELDS: &str = "{%- for field in table.fields -%}{{field.field_name}}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%}";
+    /// Column name quoted as an SQL identifier.
+    pub const fn quoted(&self) -> &'static str {
+        match self {
+{%- for field in table.fields %}
+            Self::{{ field.FieldName }} => "\"{{ field.column_name | addslashes }}\"",
+{%- endfor %}
+        }
+    }
+}
+
+impl std::fmt::Display for {{ table.struct_name }}Column {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
 {% if table.comment -%}
 	/// {{ table.comment }}
@@ -27,6 +68,12 @@ pub struct {{ table.struct_name }} {
 	{% if v.comment -%}
 	    /// {{ v.comment }} {% if v.database_field_type %} field_type: {{ v.database_field_type }}{% endif %}{% if v.default %} default: {{ v.default }}{% endif %} {% if v.default == '' %} default: ''{% endif %}
 	{% endif -%}
+	{% if v.column_name != v.field_name -%}
+	    #[sqlx(rename = "{{ v.column_name | addslashes }}")]
+	{% endif -%}
+	{% if v.rename -%}
+	    #[serde(rename = "{{ v.rename | addslashes }}")]
+	{% endif -%}
 	{% if v.is_null == 1 -%}
     	pub {{ v.field_name }}: Option<{{ v.field_type }}>,
     {%- else -%}
@@ -43,41 +90,114 @@ pub struct {{ table.struct_name }} {
 }
 
 impl {{table.struct_name}} {
+{%- if connection.mode != "none" %}
     pub async fn insert(&self) -> Result<u64> {
-    	let sql = format!("INSERT INTO {{table.table_name}} ({FIELDS}) VALUES({% for field in table.fields -%}?{% if loop.last == false %}{%- if loop.last == false -%},{%- endif -%}{% endif %}{%- endfor %})");
-    	let mut pool = SQLITE_POOL.acquire().await?;
+        self.insert_with(&*SQLITE_POOL).await
+    }
+{%- endif %}
+
+    {% if table.without_rowid -%}
+    /// Returns the number of inserted rows, WITHOUT ROWID tables have no rowid.
+    {%- elif table.rowid_alias -%}
+    /// Returns the rowid, which is also `{{ table.rowid_alias }}` when it is bound as NULL.
+    {%- else -%}
+    /// Returns the rowid of the inserted row.
+    {%- endif %}
+    pub async fn insert_with(&self, executor: impl sqlx::Executor<'_, Database = sqlx::Sqlite>) -> Result<u64> {
+    	let sql = format!("INSERT INTO {TABLE_NAME} ({FIELDS}) VALUES({% for field in table.fields -%}?{% if loop.last == false %}{%- if loop.last == false -%},{%- endif -%}{% endif %}{%- endfor %})");
     	let data = sqlx::query(&sql)
     	{%- for field in table.fields %}
             .bind(&self.{{field.field_name}})
         {%- endfor %}
-    	    .execute(&mut *pool)
+    	    .execute(executor)
             .await?
-            .last_insert_id();
+            {%- if table.without_rowid %}
+            .rows_affected();
+            {%- else %}
+            .last_insert_rowid() as u64;
+            {%- endif %}
         Ok(data)
     }
     
 
     
+{%- if connection.mode != "none" %}
+    pub async fn insert_many(rows: &[Self]) -> Result<u64> {
+        Self::insert_many_with(&*SQLITE_POOL, rows).await
+    }
+{%- endif %}
+
+    pub async fn insert_many_with(executor: impl sqlx::Acquire<'_, Database = sqlx::Sqlite>, rows: &[Self]) -> Result<u64> {
+        let mut affected = 0;
+        let mut conn = executor.acquire().await?;
+        for chunk in rows.chunks(32766 / {{ table.fields | length }}) {
+            let mut builder = sqlx::QueryBuilder::<sqlx::Sqlite>::new(format!("INSERT INTO {TABLE_NAME} ({FIELDS}) "));
+            builder.push_values(chunk, |mut b, row| {
+                b{% for field in table.fields %}.push_bind(&row.{{field.field_name}}){% endfor %};
+            });
+            affected += builder.build().execute(&mut *conn).await?.rows_affected();
+        }
+        Ok(affected)
+    }
+
+{% for indexes in table.unique_key %}
+{%- if connection.mode != "none" %}
+    pub async fn upsert_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}(&self) -> Result<u64> {
+        self.upsert_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}_with(&*SQLITE_POOL).await
+    }
+{%- endif %}
+
+    pub async fn upsert_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}_with(&self, executor: impl sqlx::Executor<'_, Database = sqlx::Sqlite>) -> Result<u64> {
+        let sql = format!("INSERT INTO {TABLE_NAME} ({FIELDS}) VALUES({% for field in table.fields -%}?{% if loop.last == false %},{% endif %}{%- endfor %}) ON CONFLICT ({% for index in indexes %}\"{{index}}\"{% if loop.last == false %}, {% endif %}{% endfor %}) {% if table.fields | length > key_columns | length %}DO UPDATE SET {% set_global n = 0 %}{%- for v in table.fields -%}{%- if v.column_name not in key_columns -%}{%- if n > 0 -%}, {% endif -%}{% set_global n = n + 1 %}\"{{v.column_name}}\" = EXCLUDED.\"{{v.column_name}}\"{%- endif -%}{%- endfor %}{% else %}DO NOTHING{% endif %}");
+        let data = sqlx::query(&sql)
+        {%- for field in table.fields %}
+            .bind(&self.{{field.field_name}})
+        {%- endfor %}
+            .execute(executor)
+            .await?.rows_affected();
+        Ok(data)
+    }
+{% endfor %}
+{%- if connection.mode != "none" %}
     pub async fn select_all() -> Result<Vec<Self>> {
-        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} {% for v in table.fields -%}{%- if v.field_name == 'is_deleted' -%} WHERE is_deleted = 0 {%- endif -%}{%- endfor -%}");
-        let mut pool = SQLITE_POOL.acquire().await?;
-        let data = sqlx::query_as::<_, Self>(&sql).fetch_all(&mut *pool).await?;
+        Self::select_all_with(&*SQLITE_POOL).await
+    }
+{%- endif %}
+
+    pub async fn select_all_with(executor: impl sqlx::Executor<'_, Database = sqlx::Sqlite>) -> Result<Vec<Self>> {
+        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} {%- if table.soft_delete %} WHERE {{ table.soft_delete.active_condition | addslashes }}{%- endif -%}");
+        let data = sqlx::query_as::<_, Self>(&sql).fetch_all(executor).await?;
         Ok(data)
     }
     
 
 {% for indexes in table.unique_key %}
     
+{%- if connection.mode != "none" %}
+    pub async fn select_optional_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%})->Result<Option<Self>>{
+        Self::select_optional_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}_with(&*SQLITE_POOL, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%}, {%- endif -%}{%- endfor -%}).await
+    }
+{%- endif %}
+
     pub async fn select_optional_by {%- for index in indexes -%}
-                        _{{index}}
-    {%- endfor %}({%- for index in indexes -%}{{index}}: {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%})->Result<Option<Self>>{
-        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} {{index}} = ? {% else %} {{index}} = ? AND {%- endif -%}{%- endfor -%}{%- for v in table.fields -%}{%- if v.field_name == 'is_deleted' -%} AND is_deleted = 0 {%- endif -%}{%- endfor -%}");
-        let mut pool = SQLITE_POOL.acquire().await?;
+                        _{{index | ident}}
+    {%- endfor %}_with(executor: impl sqlx::Executor<'_, Database = sqlx::Sqlite>, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%})->Result<Option<Self>>{
+        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} \"{{index}}\" = ? {% else %} \"{{index}}\" = ? AND {%- endif -%}{%- endfor -%}{%- if table.soft_delete %} AND {{ table.soft_delete.active_condition | addslashes }}{%- endif -%}");
         let data = sqlx::query_as::<_, Self>(&sql)
             {% for index in indexes -%}
-                {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}.bind({{index}}){%- else -%}.bind({{index}}){%- endif -%}{%- endif -%}{%- endfor -%}
+                {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind({{v.field_name}}){%- endif -%}{%- endfor -%}
             {% endfor -%}
-            .fetch_optional(&mut *pool)
+            .fetch_optional(executor)
             .await?;
         Ok(data)
     }
@@ -86,16 +206,25 @@ impl {{table.struct_name}} {
 
 {% for indexes in table.unique_key %}
     
+{%- if connection.mode != "none" %}
     pub async fn select_one_by {%- for index in indexes -%}
-                        _{{index}}
-    {%- endfor %}({%- for index in indexes -%}{{index}}: {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%})->Result<Self>{
-        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} {{index}} = ? {% else %} {{index}} = ? AND {%- endif -%}{%- endfor -%}{%- for v in table.fields -%}{%- if v.field_name == 'is_deleted' -%} AND is_deleted = 0 {%- endif -%}{%- endfor -%}");
-        let mut pool = SQLITE_POOL.acquire().await?;
+                        _{{index | ident}}
+    {%- endfor %}({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%})->Result<Self>{
+        Self::select_one_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}_with(&*SQLITE_POOL, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%}, {%- endif -%}{%- endfor -%}).await
+    }
+{%- endif %}
+
+    pub async fn select_one_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}_with(executor: impl sqlx::Executor<'_, Database = sqlx::Sqlite>, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%})->Result<Self>{
+        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} \"{{index}}\" = ? {% else %} \"{{index}}\" = ? AND {%- endif -%}{%- endfor -%}{%- if table.soft_delete %} AND {{ table.soft_delete.active_condition | addslashes }}{%- endif -%}");
         let data = sqlx::query_as::<_, Self>(&sql)
             {% for index in indexes -%}
-                {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}.bind({{index}}){%- else -%}.bind({{index}}){%- endif -%}{%- endif -%}{%- endfor -%}
+                {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind({{v.field_name}}){%- endif -%}{%- endfor -%}
             {% endfor -%}
-            .fetch_one(&mut *pool)
+            .fetch_one(executor)
             .await?;
         Ok(data)
     }
@@ -103,78 +232,566 @@ impl {{table.struct_name}} {
 {% endfor -%}
 
 {% for indexes in table.index_key %}
-   
+{%- if connection.mode != "none" %}
+    pub async fn select_many_by{%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor -%}_by_page({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%}page_no: u64, page_size: u64) -> Result<Vec<Self>> {
+        Self::select_many_by{%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor -%}_by_page_with(&*SQLITE_POOL, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}{%- endif -%}{%- endfor -%}, {%- endfor -%}page_no, page_size).await
+    }
+{%- endif %}
+
     pub async fn select_many_by{%- for index in indexes -%}
-                        _{{index}}
-    {%- endfor -%}_by_page({%- for index in indexes -%}{{index}}: {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%}page_no: u64, page_size: u64)->Result<Vec<Self>>{
-        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} {{index}} = ? {% else %} {{index}} = ? AND {%- endif -%}{%- endfor -%} {%- for v in table.fields -%}{%- if v.field_name == 'is_deleted' -%} AND is_deleted = 0 {%- endif -%}{%- endfor %} limit {},{}", (page_no - 1) * page_size, page_no * page_size);
-        let mut pool = SQLITE_POOL.acquire().await?;
+                        _{{index | ident}}
+    {%- endfor -%}_by_page_with(executor: impl sqlx::Executor<'_, Database = sqlx::Sqlite>, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%}page_no: u64, page_size: u64) -> Result<Vec<Self>> {
+        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} \"{{index}}\" = ? {% else %} \"{{index}}\" = ? AND {%- endif -%}{%- endfor -%} {%- if table.soft_delete %} AND {{ table.soft_delete.active_condition | addslashes }}{%- endif %} ORDER BY {% for key in indexes | concat(with=table.primary_key) | unique %}\"{{key}}\"{% if not loop.last %}, {% endif %}{% endfor %} LIMIT ? OFFSET ?");
         let data = sqlx::query_as::<_, Self>(&sql)
             {% for index in indexes -%}
-                {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}.bind({{index}}){%- else -%}.bind({{index}}){%- endif -%}{%- endif -%}{%- endfor -%}
+                {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind({{v.field_name}}){%- endif -%}{%- endfor -%}
             {% endfor -%}
-            .fetch_all(&mut *pool)
+            .bind(page_size as i64)
+            .bind((page_no.saturating_sub(1) * page_size) as i64)
+            .fetch_all(executor)
             .await?;
         Ok(data)
     }
-    
+{%- if connection.mode != "none" %}
+    pub async fn count_by{%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor -%}({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%}) -> Result<u64> {
+        Self::count_by{%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor -%}_with(&*SQLITE_POOL, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}{%- endif -%}{%- endfor -%}, {%- endfor -%}).await
+    }
+{%- endif %}
+
+    pub async fn count_by{%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor -%}_with(executor: impl sqlx::Executor<'_, Database = sqlx::Sqlite>, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%}) -> Result<u64> {
+        let sql = format!("SELECT COUNT(*) FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} \"{{index}}\" = ? {% else %} \"{{index}}\" = ? AND {%- endif -%}{%- endfor -%} {%- if table.soft_delete %} AND {{ table.soft_delete.active_condition | addslashes }}{%- endif %}");
+        let data: i64 = sqlx::query_scalar(&sql)
+            {% for index in indexes -%}
+                {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind({{v.field_name}}){%- endif -%}{%- endfor -%}
+            {% endfor -%}
+            .fetch_one(executor)
+            .await?;
+        Ok(data as u64)
+    }
+{%- if connection.mode != "none" %}
+    pub async fn select_page_by{%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor -%}({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%}page_no: u64, page_size: u64) -> Result<Page<Self>> {
+        Self::select_page_by{%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor -%}_with(&*SQLITE_POOL, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}{%- endif -%}{%- endfor -%}, {%- endfor -%}page_no, page_size).await
+    }
+{%- endif %}
+
+    pub async fn select_page_by{%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor -%}_with(executor: impl sqlx::Acquire<'_, Database = sqlx::Sqlite>, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%}page_no: u64, page_size: u64) -> Result<Page<Self>> {
+        let mut conn = executor.acquire().await?;
+        let sql = format!("SELECT COUNT(*) FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} \"{{index}}\" = ? {% else %} \"{{index}}\" = ? AND {%- endif -%}{%- endfor -%} {%- if table.soft_delete %} AND {{ table.soft_delete.active_condition | addslashes }}{%- endif %}");
+        let total: i64 = sqlx::query_scalar(&sql)
+            {% for index in indexes -%}
+                {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind(&{{v.field_name}}){%- endif -%}{%- endfor -%}
+            {% endfor -%}
+            .fetch_one(&mut *conn)
+            .await?;
+        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} \"{{index}}\" = ? {% else %} \"{{index}}\" = ? AND {%- endif -%}{%- endfor -%} {%- if table.soft_delete %} AND {{ table.soft_delete.active_condition | addslashes }}{%- endif %} ORDER BY {% for key in indexes | concat(with=table.primary_key) | unique %}\"{{key}}\"{% if not loop.last %}, {% endif %}{% endfor %} LIMIT ? OFFSET ?");
+        let items = sqlx::query_as::<_, Self>(&sql)
+            {% for index in indexes -%}
+                {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind(&{{v.field_name}}){%- endif -%}{%- endfor -%}
+            {% endfor -%}
+            .bind(page_size as i64)
+            .bind((page_no.saturating_sub(1) * page_size) as i64)
+            .fetch_all(&mut *conn)
+            .await?;
+        Ok(Page { items, total: total as u64, page_no, page_size })
+    }
+{% endfor -%}
+
+{% for indexes in table.unique_key %}
+{%- if indexes | length == 1 %}
+{%- set key = indexes | first %}
+{%- if connection.mode != "none" %}
+    pub async fn select_after_{{key}}(after: Option<{%- for v in table.fields -%}{%- if v.column_name == key -%}{%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}>, limit: u64) -> Result<Vec<Self>> {
+        Self::select_after_{{key}}_with(&*SQLITE_POOL, after, limit).await
+    }
+{%- endif %}
+
+    /// Keyset pagination ordered by {{key}}, pass the last {{key}} of the previous page as `after`.
+    pub async fn select_after_{{key}}_with(executor: impl sqlx::Executor<'_, Database = sqlx::Sqlite>, after: Option<{%- for v in table.fields -%}{%- if v.column_name == key -%}{%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}>, limit: u64) -> Result<Vec<Self>> {
+        let data = match after {
+            Some(after) => {
+                let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE \"{{key}}\" > ? {%- if table.soft_delete %} AND {{ table.soft_delete.active_condition | addslashes }}{%- endif %} ORDER BY \"{{key}}\" LIMIT ?");
+                sqlx::query_as::<_, Self>(&sql)
+                    .bind(after)
+                    .bind(limit as i64)
+                    .fetch_all(executor)
+                    .await?
+            }
+            None => {
+                let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} {%- if table.soft_delete %} WHERE {{ table.soft_delete.active_condition | addslashes }}{%- endif %} ORDER BY \"{{key}}\" LIMIT ?");
+                sqlx::query_as::<_, Self>(&sql)
+                    .bind(limit as i64)
+                    .fetch_all(executor)
+                    .await?
+            }
+        };
+        Ok(data)
+    }
+{%- endif %}
 {% endfor -%}
 
 {% for indexes in table.index_key %}
    
+{%- if connection.mode != "none" %}
     pub async fn select_all_by{%- for index in indexes -%}
-                        _{{index}}
-    {%- endfor -%}({%- for index in indexes -%}{{index}}: {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%})->Result<Vec<Self>>{
-        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} {{index}} = ? {% else %} {{index}} = ? AND {%- endif -%}{%- endfor -%} {%- for v in table.fields -%}{%- if v.field_name == 'is_deleted' -%} AND is_deleted = 0 {%- endif -%}{%- endfor -%}");
-        let mut pool = SQLITE_POOL.acquire().await?;
+                        _{{index | ident}}
+    {%- endfor -%}({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%})->Result<Vec<Self>>{
+        Self::select_all_by{%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor -%}_with(&*SQLITE_POOL, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%}, {%- endif -%}{%- endfor -%}).await
+    }
+{%- endif %}
+
+    pub async fn select_all_by{%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor -%}_with(executor: impl sqlx::Executor<'_, Database = sqlx::Sqlite>, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%})->Result<Vec<Self>>{
+        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} \"{{index}}\" = ? {% else %} \"{{index}}\" = ? AND {%- endif -%}{%- endfor -%} {%- if table.soft_delete %} AND {{ table.soft_delete.active_condition | addslashes }}{%- endif -%}");
         let data = sqlx::query_as::<_, Self>(&sql)
             {% for index in indexes -%}
-                {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}.bind({{index}}){%- else -%}.bind({{index}}){%- endif -%}{%- endif -%}{%- endfor -%}
+                {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind({{v.field_name}}){%- endif -%}{%- endfor -%}
             {% endfor -%}
-            .fetch_all(&mut *pool)
+            .fetch_all(executor)
             .await?;
         Ok(data)
-    }    
+    }
 {% endfor -%}
 
-{%- for v in table.fields -%}
-    {%- if v.field_name == 'is_deleted' -%}
 {% for indexes in table.unique_key %}
-    
+{%- if update_fields %}
+
+{%- if connection.mode != "none" %}
+    pub async fn update_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}(&self)->Result<u64>{
+        self.update_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}_with(&*SQLITE_POOL).await
+    }
+{%- endif %}
+
+    pub async fn update_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}_with(&self, executor: impl sqlx::Executor<'_, Database = sqlx::Sqlite>)->Result<u64>{
+        let sql = format!("UPDATE {TABLE_NAME} SET {% for v in update_fields -%}\"{{v.column_name}}\" = ?{% if not loop.last %}, {% endif %}{%- endfor %} WHERE {% for index in indexes -%} {%- if loop.last %} \"{{index}}\" = ? {% else %} \"{{index}}\" = ? AND {%- endif -%}{%- endfor -%}{%- if table.soft_delete %} AND {{ table.soft_delete.active_condition | addslashes }}{%- endif -%}");
+        let data = sqlx::query(&sql)
+            {%- for v in update_fields %}
+            .bind(&self.{{v.field_name}})
+            {%- endfor %}
+            {%- for index in indexes %}
+            {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind(&self.{{v.field_name}}){%- endif -%}{%- endfor -%}
+            {%- endfor %}
+            .execute(executor)
+            .await?.rows_affected();
+        Ok(data)
+    }
+{%- endif %}
+{% endfor -%}
+
+{% for indexes in table.unique_key %}
+
+{%- if connection.mode != "none" %}
     pub async fn delete_one_by {%- for index in indexes -%}
-                        _{{index}}
-    {%- endfor %}({%- for index in indexes -%}{{index}}: {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%})->Result<u64>{
-        let sql = format!("UPDATE {TABLE_NAME} SET is_deleted = 1 WHERE {% for index in indexes -%} {%- if loop.last %} {{index}} = ? {% else %} {{index}} = ? AND {%- endif -%}{%- endfor -%}{%- for v in table.fields -%}{%- if v.field_name == 'is_deleted' -%} AND is_deleted = 0 {%- endif -%}{%- endfor -%}");
-        let mut pool = SQLITE_POOL.acquire().await?;
+                        _{{index | ident}}
+    {%- endfor %}({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%})->Result<u64>{
+        Self::delete_one_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}_with(&*SQLITE_POOL, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%}, {%- endif -%}{%- endfor -%}).await
+    }
+{%- endif %}
+
+    pub async fn delete_one_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}_with(executor: impl sqlx::Executor<'_, Database = sqlx::Sqlite>, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%})->Result<u64>{
+        let sql = format!("{% if table.soft_delete %}UPDATE {TABLE_NAME} SET {{ table.soft_delete.delete_set | addslashes }} WHERE {% for index in indexes -%} {%- if loop.last %} \"{{index}}\" = ? {% else %} \"{{index}}\" = ? AND {%- endif -%}{%- endfor -%} AND {{ table.soft_delete.active_condition | addslashes }}{% else %}DELETE FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} \"{{index}}\" = ? {% else %} \"{{index}}\" = ? AND {%- endif -%}{%- endfor -%}{% endif %}");
         let data = sqlx::query(&sql)
             {% for index in indexes -%}
-                {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}.bind({{index}}){%- else -%}.bind({{index}}){%- endif -%}{%- endif -%}{%- endfor -%}
+                {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind({{v.field_name}}){%- endif -%}{%- endfor -%}
             {% endfor -%}
-            .execute(&mut *pool)
+            .execute(executor)
             .await?.rows_affected();
         Ok(data)
-    }  
+    }
 {% endfor -%}
 
 {% for indexes in table.index_key %}
-   
+
+{%- if connection.mode != "none" %}
+    pub async fn delete_many_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%})->Result<u64>{
+        Self::delete_many_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}_with(&*SQLITE_POOL, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%}, {%- endif -%}{%- endfor -%}).await
+    }
+{%- endif %}
+
     pub async fn delete_many_by {%- for index in indexes -%}
-                        _{{index}}
-    {%- endfor %}({%- for index in indexes -%}{{index}}: {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%})->Result<u64>{
-        let sql = format!("UPDATE {TABLE_NAME} SET is_deleted = 1 WHERE {% for index in indexes -%} {%- if loop.last %} {{index}} = ? {% else %} {{index}} = ? AND {%- endif -%}{%- endfor -%}{%- for v in table.fields -%}{%- if v.field_name == 'is_deleted' -%} AND is_deleted = 0 {%- endif -%}{%- endfor -%}");
-        let mut pool = SQLITE_POOL.acquire().await?;
+                        _{{index | ident}}
+    {%- endfor %}_with(executor: impl sqlx::Executor<'_, Database = sqlx::Sqlite>, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%})->Result<u64>{
+        let sql = format!("{% if table.soft_delete %}UPDATE {TABLE_NAME} SET {{ table.soft_delete.delete_set | addslashes }} WHERE {% for index in indexes -%} {%- if loop.last %} \"{{index}}\" = ? {% else %} \"{{index}}\" = ? AND {%- endif -%}{%- endfor -%} AND {{ table.soft_delete.active_condition | addslashes }}{% else %}DELETE FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} \"{{index}}\" = ? {% else %} \"{{index}}\" = ? AND {%- endif -%}{%- endfor -%}{% endif %}");
         let data = sqlx::query(&sql)
             {% for index in indexes -%}
-                {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}.bind({{index}}){%- else -%}.bind({{index}}){%- endif -%}{%- endif -%}{%- endfor -%}
+                {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind({{v.field_name}}){%- endif -%}{%- endfor -%}
             {% endfor -%}
-            .execute(&mut *pool)
+            .execute(executor)
             .await?.rows_affected();
         Ok(data)
     }
 {% endfor -%}
-    {%- endif -%}
-{%- endfor -%}
+
+{%- if table.soft_delete -%}
+{% for indexes in table.unique_key %}
+
+{%- if connection.mode != "none" %}
+    pub async fn restore_one_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%})->Result<u64>{
+        Self::restore_one_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}_with(&*SQLITE_POOL, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%}, {%- endif -%}{%- endfor -%}).await
+    }
+{%- endif %}
+
+    pub async fn restore_one_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}_with(executor: impl sqlx::Executor<'_, Database = sqlx::Sqlite>, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%})->Result<u64>{
+        let sql = format!("UPDATE {TABLE_NAME} SET {{ table.soft_delete.restore_set | addslashes }} WHERE {% for index in indexes -%} {%- if loop.last %} \"{{index}}\" = ? {% else %} \"{{index}}\" = ? AND {%- endif -%}{%- endfor -%} AND {{ table.soft_delete.deleted_condition | addslashes }}");
+        let data = sqlx::query(&sql)
+            {% for index in indexes -%}
+                {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind({{v.field_name}}){%- endif -%}{%- endfor -%}
+            {% endfor -%}
+            .execute(executor)
+            .await?.rows_affected();
+        Ok(data)
+    }
+{% endfor -%}
+
+{% for indexes in table.index_key %}
+
+{%- if connection.mode != "none" %}
+    pub async fn restore_many_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}({%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%})->Result<u64>{
+        Self::restore_many_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}_with(&*SQLITE_POOL, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%}, {%- endif -%}{%- endfor -%}).await
+    }
+{%- endif %}
+
+    pub async fn restore_many_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}_with(executor: impl sqlx::Executor<'_, Database = sqlx::Sqlite>, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%})->Result<u64>{
+        let sql = format!("UPDATE {TABLE_NAME} SET {{ table.soft_delete.restore_set | addslashes }} WHERE {% for index in indexes -%} {%- if loop.last %} \"{{index}}\" = ? {% else %} \"{{index}}\" = ? AND {%- endif -%}{%- endfor -%} AND {{ table.soft_delete.deleted_condition | addslashes }}");
+        let data = sqlx::query(&sql)
+            {% for index in indexes -%}
+                {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind({{v.field_name}}){%- endif -%}{%- endfor -%}
+            {% endfor -%}
+            .execute(executor)
+            .await?.rows_affected();
+        Ok(data)
+    }
+{% endfor -%}
+{%- endif -%}
+}
+{% if update_fields %}
+/// Partial update of {{ table.struct_name }}, only the `Some` fields are written.
+#[derive(Debug, Clone, Default)]
+pub struct {{ table.struct_name }}Update {
+{%- for v in update_fields %}
+    pub {{ v.field_name }}: Option<{% if v.is_null == 1 or v.field_type == 'time::OffsetDateTime' or v.field_type == 'chrono::NaiveDateTime' %}Option<{{ v.field_type }}>{% else %}{{ v.field_type }}{% endif %}>,
+{%- endfor %}
+}
+
+impl {{ table.struct_name }}Update {
+    pub fn new() -> Self {
+        Self::default()
+    }
+{% for v in update_fields %}
+    pub fn {{ v.field_name }}(mut self, value: {% if v.is_null == 1 or v.field_type == 'time::OffsetDateTime' or v.field_type == 'chrono::NaiveDateTime' %}Option<{{ v.field_type }}>{% else %}{{ v.field_type }}{% endif %}) -> Self {
+        self.{{ v.field_name }} = Some(value);
+        self
+    }
+{% endfor -%}
+
+{% for indexes in table.unique_key %}
+
+{%- if connection.mode != "none" %}
+    pub async fn update_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}(&self, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%})->Result<u64>{
+        self.update_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}_with(&*SQLITE_POOL, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%}, {%- endif -%}{%- endfor -%}).await
+    }
+{%- endif %}
+
+    pub async fn update_by {%- for index in indexes -%}
+                        _{{index | ident}}
+    {%- endfor %}_with(&self, executor: impl sqlx::Executor<'_, Database = sqlx::Sqlite>, {%- for index in indexes -%}{%- for v in table.fields -%}{%- if v.column_name == index -%}{{v.field_name}}: {%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%})->Result<u64>{
+        let mut sets: Vec<&str> = vec![];
+        {%- for v in update_fields %}
+        if self.{{ v.field_name }}.is_some() {
+            sets.push("\"{{ v.column_name }}\" = ?");
+        }
+        {%- endfor %}
+        if sets.is_empty() {
+            return Ok(0);
+        }
+        let sql = format!("UPDATE {TABLE_NAME} SET {} WHERE {% for index in indexes -%} {%- if loop.last %} \"{{index}}\" = ? {% else %} \"{{index}}\" = ? AND {%- endif -%}{%- endfor -%}{%- if table.soft_delete %} AND {{ table.soft_delete.active_condition | addslashes }}{%- endif -%}", sets.join(", "));
+        let mut query = sqlx::query(&sql);
+        {%- for v in update_fields %}
+        if let Some(value) = &self.{{ v.field_name }} {
+            query = query.bind(value);
+        }
+        {%- endfor %}
+        let data = query
+            {% for index in indexes -%}
+                {%- for v in table.fields -%}{%- if v.column_name == index -%}.bind({{v.field_name}}){%- endif -%}{%- endfor -%}
+            {% endfor -%}
+            .execute(executor)
+            .await?.rows_affected();
+        Ok(data)
+    }
+{% endfor -%}
+}
+{%- endif %}
+
+/// Typed filter builder for {{ table.struct_name }}, conditions are joined with AND.
+#[derive(Default)]
+pub struct {{ table.struct_name }}Query {
+    conditions: Vec<String>,
+    arguments: sqlx::sqlite::SqliteArguments<'static>,
+    error: Option<sqlx::error::BoxDynError>,
+    order_by: Vec<String>,
+    limit: Option<u64>,
+    offset: Option<u64>,
+}
+
+impl {{ table.struct_name }}Query {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn bind<T>(&mut self, value: T) -> String
+    where
+        T: for<'q> sqlx::Encode<'q, sqlx::Sqlite> + sqlx::Type<sqlx::Sqlite> + 'static,
+    {
+        use sqlx::Arguments;
+        if let Err(err) = self.arguments.add(value) {
+            self.error.get_or_insert(err);
+        }
+        "?".to_string()
+    }
+
+    fn push<T>(mut self, column: &str, op: &str, value: T) -> Self
+    where
+        T: for<'q> sqlx::Encode<'q, sqlx::Sqlite> + sqlx::Type<sqlx::Sqlite> + 'static,
+    {
+        let placeholder = self.bind(value);
+        self.conditions.push(format!("{column} {op} {placeholder}"));
+        self
+    }
+
+    fn push_in<T>(mut self, column: &str, values: impl IntoIterator<Item = T>) -> Self
+    where
+        T: for<'q> sqlx::Encode<'q, sqlx::Sqlite> + sqlx::Type<sqlx::Sqlite> + 'static,
+    {
+        let placeholders: Vec<String> = values.into_iter().map(|value| self.bind(value)).collect();
+        if placeholders.is_empty() {
+            self.conditions.push("1 = 0".to_string());
+        } else {
+            self.conditions.push(format!("{column} IN ({})", placeholders.join(", ")));
+        }
+        self
+    }
+{% for v in table.fields %}
+{%- set name = v.field_name | replace(from="r#", to="") | trim_end_matches(pat="_") %}
+{%- set field_type = v.field_type | inner_type %}
+{%- if field_type == 'String' %}{% set ty = "impl Into<String>" %}{% set item = "String" %}{% set conv = ".into()" %}{% else %}{% set ty = field_type %}{% set item = field_type %}{% set conv = "" %}{% endif %}
+
+    pub fn {{ name }}_eq(self, value: {{ ty }}) -> Self {
+        self.push("\"{{ v.column_name }}\"", "=", value{{ conv }})
+    }
+
+    pub fn {{ name }}_ne(self, value: {{ ty }}) -> Self {
+        self.push("\"{{ v.column_name }}\"", "<>", value{{ conv }})
+    }
+
+    pub fn {{ name }}_in(self, values: impl IntoIterator<Item = {{ item }}>) -> Self {
+        self.push_in("\"{{ v.column_name }}\"", values)
+    }
+{%- if field_type == 'String' %}
+
+    pub fn {{ name }}_like(self, pattern: impl Into<String>) -> Self {
+        self.push("\"{{ v.column_name }}\"", "LIKE", pattern.into())
+    }
+{%- elif field_type not in ['bool', 'Vec<u8>'] %}
+
+    pub fn {{ name }}_gt(self, value: {{ ty }}) -> Self {
+        self.push("\"{{ v.column_name }}\"", ">", value)
+    }
+
+    pub fn {{ name }}_ge(self, value: {{ ty }}) -> Self {
+        self.push("\"{{ v.column_name }}\"", ">=", value)
+    }
+
+    pub fn {{ name }}_lt(self, value: {{ ty }}) -> Self {
+        self.push("\"{{ v.column_name }}\"", "<", value)
+    }
+
+    pub fn {{ name }}_le(self, value: {{ ty }}) -> Self {
+        self.push("\"{{ v.column_name }}\"", "<=", value)
+    }
+{%- endif %}
+{%- if v.is_null == 1 %}
+
+    pub fn {{ name }}_is_null(mut self) -> Self {
+        self.conditions.push("\"{{ v.column_name }}\" IS NULL".to_string());
+        self
+    }
+
+    pub fn {{ name }}_is_not_null(mut self) -> Self {
+        self.conditions.push("\"{{ v.column_name }}\" IS NOT NULL".to_string());
+        self
+    }
+{%- endif %}
+
+    pub fn order_by_{{ name }}_asc(mut self) -> Self {
+        self.order_by.push("\"{{ v.column_name }}\" ASC".to_string());
+        self
+    }
+
+    pub fn order_by_{{ name }}_desc(mut self) -> Self {
+        self.order_by.push("\"{{ v.column_name }}\" DESC".to_string());
+        self
+    }
+{%- endfor %}
+
+    pub fn order_by_asc(mut self, column: {{ table.struct_name }}Column) -> Self {
+        self.order_by.push(format!("{} ASC", column.quoted()));
+        self
+    }
+
+    pub fn order_by_desc(mut self, column: {{ table.struct_name }}Column) -> Self {
+        self.order_by.push(format!("{} DESC", column.quoted()));
+        self
+    }
+
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    fn where_sql(&self) -> String {
+        let conditions: Vec<&str> = self
+            .conditions
+            .iter()
+            .map(|v| v.as_str())
+            {%- if table.soft_delete %}
+            .chain(["{{ table.soft_delete.active_condition | addslashes }}"])
+            {%- endif %}
+            .collect();
+        if conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", conditions.join(" AND "))
+        }
+    }
+
+    fn select_sql(&self) -> String {
+        let mut sql = format!("SELECT {FIELDS} FROM {TABLE_NAME}{}", self.where_sql());
+        if !self.order_by.is_empty() {
+            sql.push_str(&format!(" ORDER BY {}", self.order_by.join(", ")));
+        }
+        if self.limit.is_some() || self.offset.is_some() {
+            sql.push_str(&format!(" LIMIT {}", self.limit.unwrap_or(i64::MAX as u64)));
+        }
+        if let Some(offset) = self.offset {
+            sql.push_str(&format!(" OFFSET {offset}"));
+        }
+        sql
+    }
+{%- if connection.mode != "none" %}
+
+    pub async fn fetch_all(self) -> Result<Vec<{{ table.struct_name }}>> {
+        self.fetch_all_with(&*SQLITE_POOL).await
+    }
+
+    pub async fn fetch_optional(self) -> Result<Option<{{ table.struct_name }}>> {
+        self.fetch_optional_with(&*SQLITE_POOL).await
+    }
+
+    pub async fn fetch_one(self) -> Result<{{ table.struct_name }}> {
+        self.fetch_one_with(&*SQLITE_POOL).await
+    }
+
+    pub async fn count(self) -> Result<u64> {
+        self.count_with(&*SQLITE_POOL).await
+    }
+{%- endif %}
+
+    pub async fn fetch_all_with(self, executor: impl sqlx::Executor<'_, Database = sqlx::Sqlite>) -> Result<Vec<{{ table.struct_name }}>> {
+        let sql = self.select_sql();
+        if let Some(err) = self.error {
+            return Err(sqlx::Error::Encode(err));
+        }
+        let data = sqlx::query_as_with::<_, {{ table.struct_name }}, _>(&sql, self.arguments)
+            .fetch_all(executor)
+            .await?;
+        Ok(data)
+    }
+
+    pub async fn fetch_optional_with(self, executor: impl sqlx::Executor<'_, Database = sqlx::Sqlite>) -> Result<Option<{{ table.struct_name }}>> {
+        let sql = self.select_sql();
+        if let Some(err) = self.error {
+            return Err(sqlx::Error::Encode(err));
+        }
+        let data = sqlx::query_as_with::<_, {{ table.struct_name }}, _>(&sql, self.arguments)
+            .fetch_optional(executor)
+            .await?;
+        Ok(data)
+    }
+
+    pub async fn fetch_one_with(self, executor: impl sqlx::Executor<'_, Database = sqlx::Sqlite>) -> Result<{{ table.struct_name }}> {
+        let sql = self.select_sql();
+        if let Some(err) = self.error {
+            return Err(sqlx::Error::Encode(err));
+        }
+        let data = sqlx::query_as_with::<_, {{ table.struct_name }}, _>(&sql, self.arguments)
+            .fetch_one(executor)
+            .await?;
+        Ok(data)
+    }
+
+    pub async fn count_with(self, executor: impl sqlx::Executor<'_, Database = sqlx::Sqlite>) -> Result<u64> {
+        let sql = format!("SELECT COUNT(*) FROM {TABLE_NAME}{}", self.where_sql());
+        if let Some(err) = self.error {
+            return Err(sqlx::Error::Encode(err));
+        }
+        let data: i64 = sqlx::query_scalar_with(&sql, self.arguments)
+            .fetch_one(executor)
+            .await?;
+        Ok(data as u64)
+    }
 }
-    "#,
-    )
-});
+    "#;
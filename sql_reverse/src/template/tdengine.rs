@@ -1,30 +1,90 @@
-use std::sync::{LazyLock, RwLock};
-
-pub static TDENGINE_TEMPLATE: LazyLock<RwLock<&str>> = LazyLock::new(|| {
-    RwLock::new(
-        r#"
+pub static TDENGINE_TEMPLATE: &str = r#"
 use serde::{Deserialize, Serialize};
-use taos::{AsyncQueryable, AsyncFetchable};
+use taos::{AsyncBindable, AsyncQueryable, AsyncFetchable, ColumnView, Stmt};
 use futures::TryStreamExt;
 use super::Result;
-use super::TDENGINE_BUILDER;
+use super::TDENGINE_POOL;
+{%- set ts = table.fields | first %}
+{%- set numbers = ["i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64", "f32", "f64"] %}
+{%- set_global has_tags = false %}{% set_global has_numbers = false %}
+{%- for v in table.fields %}{% if v.is_tag %}{% set_global has_tags = true %}{% elif v.field_type in numbers %}{% set_global has_numbers = true %}{% endif %}{% endfor %}
+{%- if table.time_precision == "us" %}{% set ts_fn = "timestamp_micros()" %}{% set ts_view = "from_micros_timestamp" %}{% set ts_variant = "Microseconds" %}
+{%- elif table.time_precision == "ns" %}{% set ts_fn = "timestamp_nanos_opt().unwrap_or_default()" %}{% set ts_view = "from_nanos_timestamp" %}{% set ts_variant = "Nanoseconds" %}
+{%- else %}{% set ts_fn = "timestamp_millis()" %}{% set ts_view = "from_millis_timestamp" %}{% set ts_variant = "Milliseconds" %}{% endif %}
+
+pub const TABLE_NAME: &str = "`{{table.table_name}}`";
+
+{% if has_tags %}/// Regular columns, tags are listed in `TAG_FIELDS`.
+{% endif -%}
+pub const FIELDS: &str = "{% set_global first = true %}{% for field in table.fields %}{% if field.is_tag == false %}{% if first == false %},{% endif %}`{{field.column_name}}`{% set_global first = false %}{% endif %}{% endfor %}";
+{% if has_tags %}
+pub const TAG_FIELDS: &str = "{% set_global first = true %}{% for field in table.fields %}{% if field.is_tag %}{% if first == false %},{% endif %}`{{field.column_name}}`{% set_global first = false %}{% endif %}{% endfor %}";
+{% endif %}
+{% for field in table.fields -%}
+pub const COLUMN_{{ field.field_name | replace(from="r#", to="") | upper }}: &str = "{{ field.column_name | addslashes }}";
+{% endfor %}
+/// Columns of `{{ table.table_name }}`{% if has_tags %}, tags included{% endif %}.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum {{ table.struct_name }}Column {
+{%- for field in table.fields %}
+    {{ field.FieldName }},
+{%- endfor %}
+}
+
+impl {{ table.struct_name }}Column {
+    pub const ALL: [Self; {{ table.fields | length }}] = [
+{%- for field in table.fields %}
+        Self::{{ field.FieldName }},
+{%- endfor %}
+    ];
+
+    /// Raw column name.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+{%- for field in table.fields %}
+            Self::{{ field.FieldName }} => COLUMN_{{ field.field_name | replace(from="r#", to="") | upper }},
+{%- endfor %}
+        }
+    }
+
+    /// Column name quoted as an SQL identifier.
+    pub const fn quoted(&self) -> &'static str {
+        match self {
+{%- for field in table.fields %}
+            Self::{{ field.FieldName }} => "`{{ field.column_name | addslashes }}`",
+{%- endfor %}
+        }
+    }
 
-pub const TABLE_NAME: &str = "{{table.table_name}}";
+    /// Whether the column is a tag.
+    pub const fn is_tag(&self) -> bool {
+        {% if has_tags %}matches!(self, {% set_global first = true %}{% for field in table.fields %}{% if field.is_tag %}{% if first == false %} | {% endif %}Self::{{ field.FieldName }}{% set_global first = false %}{% endif %}{% endfor %}){% else %}false{% endif %}
+    }
+}
 
-pub const FIELDS: &str = "{%- for field in table.fields -%}{{field.field_name}}{%- if loop.last == false -%},{%- endif -%}{%- endfor -%}";
+impl std::fmt::Display for {{ table.struct_name }}Column {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
 {% if table.comment -%}
 	/// {{ table.comment }}
 {% endif -%}
-{% for index in table.unique_key -%}
-    /// Tags：{{index}}
-{% endfor -%}
+{% if table.kind == "supertable" -%}
+    /// Supertable, rows are written to subtables with [`{{ table.struct_name }}::insert_into_subtable`].
+{% elif table.kind == "child" -%}
+    /// Child table, its tags are read with [`{{ table.struct_name }}::select_tags`].
+{% endif -%}
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct {{ table.struct_name }} {
-{%- for v in table.fields %}
+{%- for v in table.fields %}{% if v.is_tag == false %}
 	{% if v.comment -%}
 	    /// {{ v.comment }} {% if v.database_field_type %} field_type: {{ v.database_field_type }}{% endif %}{% if v.default %} default: {{ v.default }}{% endif %} {% if v.default == '' %} default: ''{% endif %}
 	{% endif -%}
+	{% if v.column_name != v.field_name -%}
+	    #[serde(rename = "{{ v.column_name | addslashes }}")]
+	{% endif -%}
 	{% if v.is_null == 1 -%}
     	pub {{ v.field_name }}: Option<{{ v.field_type }}>,
     {%- else -%}
@@ -34,70 +94,156 @@ pub struct {{ table.struct_name }} {
             pub {{ v.field_name }}: {{ v.field_type }},
         {%- endif -%}
     {%- endif -%}
-{%- endfor %}
+{%- endif %}{%- endfor %}
 }
-
+{% if has_tags %}
+/// Tags of `{{ table.table_name }}`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct {{ table.struct_name }}Tags {
+{%- for v in table.fields %}{% if v.is_tag %}
+	{% if v.database_field_type -%}
+	    /// field_type: {{ v.database_field_type }}
+	{% endif -%}
+	{% if v.column_name != v.field_name -%}
+	    #[serde(rename = "{{ v.column_name | addslashes }}")]
+	{% endif -%}
+    pub {{ v.field_name }}: Option<{{ v.field_type }}>,
+{%- endif %}{%- endfor %}
+}
+{% endif %}
+{%- if has_numbers %}
+/// One `INTERVAL` window of `{{ table.table_name }}`, numeric columns hold the chosen aggregate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct {{ table.struct_name }}Window {
+    pub window_start: Option<chrono::NaiveDateTime>,
+    pub window_end: Option<chrono::NaiveDateTime>,
+{%- for v in table.fields %}{% if v.is_tag == false and v.field_type in numbers %}
+	{% if v.column_name != v.field_name -%}
+	    #[serde(rename = "{{ v.column_name | addslashes }}")]
+	{% endif -%}
+    pub {{ v.field_name }}: Option<f64>,
+{%- endif %}{%- endfor %}
+}
+{% endif %}
 impl {{table.struct_name}} {
     pub async fn select_all() -> Result<Vec<Self>> {
-        let taos = TDENGINE_BUILDER.build().await?;
-        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} {% for v in table.fields -%}{%- if v.field_name == 'is_deleted' -%} WHERE is_deleted = 0 {%- endif -%}{%- endfor -%}");
+        let taos = TDENGINE_POOL.get().await.map_err(taos::Error::from_any)?;
+        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME}");
         let data: Vec<Self> = taos.query(&sql).await?.deserialize().try_collect().await?;
         Ok(data)
     }
-
-{% for indexes in table.unique_key %}
-
-    pub async fn select_optional_by {%- for index in indexes -%}
-                        _{{index}}
-    {%- endfor %}({%- for index in indexes -%}{{index}}: {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%})->Result<Vec<Self>>{
-        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} {{index}} = '{}' {% else %} {{index}} = '{}' AND {%- endif -%}{%- endfor -%}{%- for v in table.fields -%}{%- if v.field_name == 'is_deleted' -%} AND is_deleted = 0 {%- endif -%}{%- endfor -%} LIMIT 1", {%- for index in indexes -%}{{index}},{%- endfor -%});
-        let taos = TDENGINE_BUILDER.build().await?;
-        let data: Vec<Self> = taos.query(&sql).await?.deserialize().try_collect().await?;
-        Ok(data)
+{% if table.kind == "supertable" %}
+    /// Inserts rows into the subtable `tbname` with a prepared statement,
+    /// the subtable is created from `{{ table.table_name }}` with the given tags when it does not exist.
+    pub async fn insert_into_subtable(tbname: &str, tags: &{{ table.struct_name }}Tags, rows: &[Self]) -> Result<usize> {
+        if rows.is_empty() {
+            return Ok(0);
+        }
+        let taos = TDENGINE_POOL.get().await.map_err(taos::Error::from_any)?;
+        let mut stmt = Stmt::init(&taos).await?;
+        stmt.prepare(&format!("INSERT INTO ? USING {TABLE_NAME} ({TAG_FIELDS}) TAGS ({% set_global first = true %}{% for v in table.fields %}{% if v.is_tag %}{% if first == false %}, {% endif %}?{% set_global first = false %}{% endif %}{% endfor %}) ({FIELDS}) VALUES ({% set_global first = true %}{% for v in table.fields %}{% if v.is_tag == false %}{% if first == false %}, {% endif %}?{% set_global first = false %}{% endif %}{% endfor %})")).await?;
+        stmt.set_tbname_tags(tbname, &[
+{%- for v in table.fields %}{% if v.is_tag %}{%- if v.field_type == 'chrono::NaiveDateTime' %}
+            tags.{{v.field_name}}.map(|v| taos::Value::Timestamp(taos::taos_query::common::Timestamp::{{ts_variant}}(v.and_utc().{{ts_fn}}))).unwrap_or(taos::Value::Null(taos::Ty::Timestamp)),
+{%- elif v.field_type == 'String' %}
+            {% if v.database_field_type is starting_with("NCHAR") %}tags.{{v.field_name}}.clone().map(taos::Value::NChar).unwrap_or(taos::Value::Null(taos::Ty::NChar)){% elif v.database_field_type is starting_with("VARBINARY") %}tags.{{v.field_name}}.as_ref().map(|v| taos::Value::VarBinary(v.as_bytes().to_vec().into())).unwrap_or(taos::Value::Null(taos::Ty::VarBinary)){% elif v.database_field_type is starting_with("GEOMETRY") %}tags.{{v.field_name}}.as_ref().map(|v| taos::Value::Geometry(v.as_bytes().to_vec().into())).unwrap_or(taos::Value::Null(taos::Ty::Geometry)){% else %}tags.{{v.field_name}}.clone().map(taos::Value::VarChar).unwrap_or(taos::Value::Null(taos::Ty::VarChar)){% endif %},
+{%- elif v.field_type == 'serde_json::Value' %}
+            tags.{{v.field_name}}.clone().map(taos::Value::Json).unwrap_or(taos::Value::Null(taos::Ty::Json)),
+{%- else %}
+            taos::Value::from(tags.{{v.field_name}}),
+{%- endif %}
+{%- endif %}{% endfor %}
+        ]).await?;
+        stmt.bind(&[
+{%- for v in table.fields %}{% if v.is_tag == false %}{%- if v.field_type == 'chrono::NaiveDateTime' %}
+            ColumnView::{{ts_view}}(rows.iter().map(|row| row.{{v.field_name}}.map(|v| v.and_utc().{{ts_fn}})).collect::<Vec<_>>()),
+{%- elif v.field_type == 'String' %}
+            ColumnView::{% if v.database_field_type is starting_with("NCHAR") %}from_nchar::<&str, _, _, _>{% elif v.database_field_type is starting_with("VARBINARY") or v.database_field_type == "BLOB" %}from_bytes::<&[u8], _, _, _>{% elif v.database_field_type is starting_with("GEOMETRY") %}from_geobytes::<&[u8], _, _, _>{% else %}from_varchar::<&str, _, _, _>{% endif %}(rows.iter().map(|row| row.{{v.field_name}}{% if v.is_null == 1 %}.as_deref(){% else %}.as_str(){% endif %}{% if v.database_field_type is starting_with("VARBINARY") or v.database_field_type is starting_with("GEOMETRY") or v.database_field_type == "BLOB" %}{% if v.is_null == 1 %}.map(str::as_bytes){% else %}.as_bytes(){% endif %}{% endif %}).collect::<Vec<_>>()),
+{%- elif v.field_type == 'serde_json::Value' %}
+            ColumnView::from_json::<String, _, _, _>(rows.iter().map(|row| row.{{v.field_name}}{% if v.is_null == 1 %}.as_ref().map(|v| v.to_string()){% else %}.to_string(){% endif %}).collect::<Vec<_>>()),
+{%- else %}
+            ColumnView::{% if v.field_type == 'bool' %}from_bools{% elif v.field_type == 'i8' %}from_tiny_ints{% elif v.field_type == 'i16' %}from_small_ints{% elif v.field_type == 'i32' %}from_ints{% elif v.field_type == 'i64' %}from_big_ints{% elif v.field_type == 'u8' %}from_unsigned_tiny_ints{% elif v.field_type == 'u16' %}from_unsigned_small_ints{% elif v.field_type == 'u32' %}from_unsigned_ints{% elif v.field_type == 'u64' %}from_unsigned_big_ints{% elif v.field_type == 'f32' %}from_floats{% else %}from_doubles{% endif %}(rows.iter().map(|row| row.{{v.field_name}}).collect::<Vec<_>>()),
+{%- endif %}
+{%- endif %}{% endfor %}
+        ]).await?;
+        stmt.add_batch().await?;
+        Ok(stmt.execute().await?)
     }
 
-{% endfor -%}
-
-{% for indexes in table.unique_key %}
-
-    pub async fn select_all_by{%- for index in indexes -%}
-                        _{{index}}
-    {%- endfor -%}({%- for index in indexes -%}{{index}}: {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%})->Result<Vec<Self>>{
-        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} {{index}} = '{}' {% else %} {{index}} = '{}' AND {%- endif -%}{%- endfor -%} {%- for v in table.fields -%}{%- if v.field_name == 'is_deleted' -%} AND is_deleted = 0 {%- endif -%}{%- endfor -%}", {%- for index in indexes -%}{{index}},{%- endfor -%});
-        let taos = TDENGINE_BUILDER.build().await?;
-        let data: Vec<Self> = taos.query(&sql).await?.deserialize().try_collect().await?;
-        Ok(data)
+    /// Tags of the subtable `tbname`, `None` when it does not exist.
+    pub async fn select_tags(tbname: &str) -> Result<Option<{{ table.struct_name }}Tags>> {
+        let taos = TDENGINE_POOL.get().await.map_err(taos::Error::from_any)?;
+        let sql = format!("SELECT {TAG_FIELDS} FROM {TABLE_NAME} WHERE tbname = {} LIMIT 1", quote(tbname));
+        let data: Vec<{{ table.struct_name }}Tags> = taos.query(&sql).await?.deserialize().try_collect().await?;
+        Ok(data.into_iter().next())
+    }
+{% else %}
+    pub async fn insert(&self) -> Result<usize> {
+        Self::insert_many(std::slice::from_ref(self)).await
     }
-{% endfor -%}
-
-{% for indexes in table.unique_key %}
 
-    pub async fn select_many_by{%- for index in indexes -%}
-                        _{{index}}
-    {%- endfor -%}_by_page({%- for index in indexes -%}{{index}}: {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%}page_no: u64, page_size: u64)->Result<Vec<Self>>{
-        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE {% for index in indexes -%} {%- if loop.last %} {{index}} = '{}' {% else %} {{index}} = '{}' AND {%- endif -%}{%- endfor -%} {%- for v in table.fields -%}{%- if v.field_name == 'is_deleted' -%} AND is_deleted = 0 {%- endif -%}{%- endfor %} LIMIT {}, {}", {%- for index in indexes -%}{{index}},{%- endfor -%} (page_no - 1) * page_size, page_size);
-        let taos = TDENGINE_BUILDER.build().await?;
+    /// Inserts rows with a prepared statement, values are bound rather than formatted into the SQL.
+    pub async fn insert_many(rows: &[Self]) -> Result<usize> {
+        if rows.is_empty() {
+            return Ok(0);
+        }
+        let taos = TDENGINE_POOL.get().await.map_err(taos::Error::from_any)?;
+        let mut stmt = Stmt::init(&taos).await?;
+        stmt.prepare(&format!("INSERT INTO {TABLE_NAME} ({FIELDS}) VALUES ({% set_global first = true %}{% for v in table.fields %}{% if v.is_tag == false %}{% if first == false %}, {% endif %}?{% set_global first = false %}{% endif %}{% endfor %})")).await?;
+        stmt.bind(&[
+{%- for v in table.fields %}{% if v.is_tag == false %}{%- if v.field_type == 'chrono::NaiveDateTime' %}
+            ColumnView::{{ts_view}}(rows.iter().map(|row| row.{{v.field_name}}.map(|v| v.and_utc().{{ts_fn}})).collect::<Vec<_>>()),
+{%- elif v.field_type == 'String' %}
+            ColumnView::{% if v.database_field_type is starting_with("NCHAR") %}from_nchar::<&str, _, _, _>{% elif v.database_field_type is starting_with("VARBINARY") or v.database_field_type == "BLOB" %}from_bytes::<&[u8], _, _, _>{% elif v.database_field_type is starting_with("GEOMETRY") %}from_geobytes::<&[u8], _, _, _>{% else %}from_varchar::<&str, _, _, _>{% endif %}(rows.iter().map(|row| row.{{v.field_name}}{% if v.is_null == 1 %}.as_deref(){% else %}.as_str(){% endif %}{% if v.database_field_type is starting_with("VARBINARY") or v.database_field_type is starting_with("GEOMETRY") or v.database_field_type == "BLOB" %}{% if v.is_null == 1 %}.map(str::as_bytes){% else %}.as_bytes(){% endif %}{% endif %}).collect::<Vec<_>>()),
+{%- elif v.field_type == 'serde_json::Value' %}
+            ColumnView::from_json::<String, _, _, _>(rows.iter().map(|row| row.{{v.field_name}}{% if v.is_null == 1 %}.as_ref().map(|v| v.to_string()){% else %}.to_string(){% endif %}).collect::<Vec<_>>()),
+{%- else %}
+            ColumnView::{% if v.field_type == 'bool' %}from_bools{% elif v.field_type == 'i8' %}from_tiny_ints{% elif v.field_type == 'i16' %}from_small_ints{% elif v.field_type == 'i32' %}from_ints{% elif v.field_type == 'i64' %}from_big_ints{% elif v.field_type == 'u8' %}from_unsigned_tiny_ints{% elif v.field_type == 'u16' %}from_unsigned_small_ints{% elif v.field_type == 'u32' %}from_unsigned_ints{% elif v.field_type == 'u64' %}from_unsigned_big_ints{% elif v.field_type == 'f32' %}from_floats{% else %}from_doubles{% endif %}(rows.iter().map(|row| row.{{v.field_name}}).collect::<Vec<_>>()),
+{%- endif %}
+{%- endif %}{% endfor %}
+        ]).await?;
+        stmt.add_batch().await?;
+        Ok(stmt.execute().await?)
+    }
+{% if has_tags %}
+    /// Tags of this child table, `None` when it holds no rows.
+    pub async fn select_tags() -> Result<Option<{{ table.struct_name }}Tags>> {
+        let taos = TDENGINE_POOL.get().await.map_err(taos::Error::from_any)?;
+        let sql = format!("SELECT {TAG_FIELDS} FROM {TABLE_NAME} LIMIT 1");
+        let data: Vec<{{ table.struct_name }}Tags> = taos.query(&sql).await?.deserialize().try_collect().await?;
+        Ok(data.into_iter().next())
+    }
+{% endif %}
+{%- endif %}
+    /// Rows with `{{ ts.column_name }}` in `[start, end)`, ordered by time.
+    pub async fn select_by_ts_range(start: chrono::NaiveDateTime, end: chrono::NaiveDateTime) -> Result<Vec<Self>> {
+        let taos = TDENGINE_POOL.get().await.map_err(taos::Error::from_any)?;
+        let sql = format!("SELECT {FIELDS} FROM {TABLE_NAME} WHERE `{{ ts.column_name }}` >= {} AND `{{ ts.column_name }}` < {} ORDER BY `{{ ts.column_name }}`", start.and_utc().{{ts_fn}}, end.and_utc().{{ts_fn}});
         let data: Vec<Self> = taos.query(&sql).await?.deserialize().try_collect().await?;
         Ok(data)
     }
-{% endfor -%}
-
-{%- for v in table.fields -%}
-    {%- if v.field_name == 'is_deleted' -%}
-{% for indexes in table.unique_key %}
 
-    pub async fn delete_one_by {%- for index in indexes -%}
-                        _{{index}}
-    {%- endfor %}({%- for index in indexes -%}{{index}}: {%- for v in table.fields -%}{%- if v.field_name == index -%}{%- if v.field_type == 'String' -%}&str{%- else -%}{{v.field_type}}{%- endif -%}{%- endif -%}{%- endfor -%},{%- endfor -%})->Result<()>{
-        let sql = format!("UPDATE {TABLE_NAME} SET is_deleted = 1 WHERE {% for index in indexes -%} {%- if loop.last %} {{index}} = '{}' {% else %} {{index}} = '{}' AND {%- endif -%}{%- endfor -%}{%- for v in table.fields -%}{%- if v.field_name == 'is_deleted' -%} AND is_deleted = 0 {%- endif -%}{%- endfor -%}", {%- for index in indexes -%}{{index}},{%- endfor -%});
-        let taos = TDENGINE_BUILDER.build().await?;
-        taos.exec(&sql).await?;
-        Ok(())
+    /// Deletes rows with `{{ ts.column_name }}` in `[start, end)`.
+    pub async fn delete_by_ts_range(start: chrono::NaiveDateTime, end: chrono::NaiveDateTime) -> Result<usize> {
+        let taos = TDENGINE_POOL.get().await.map_err(taos::Error::from_any)?;
+        let sql = format!("DELETE FROM {TABLE_NAME} WHERE `{{ ts.column_name }}` >= {} AND `{{ ts.column_name }}` < {}", start.and_utc().{{ts_fn}}, end.and_utc().{{ts_fn}});
+        Ok(taos.exec(&sql).await?)
     }
-{% endfor -%}
-    {%- endif -%}
-{%- endfor -%}
+{% if has_numbers %}
+    /// Aggregates numeric columns over `[start, end)` in windows of `interval`, e.g. `Duration::from_secs(60)`.
+    pub async fn select_interval(start: chrono::NaiveDateTime, end: chrono::NaiveDateTime, interval: std::time::Duration, aggregate: super::Aggregate) -> Result<Vec<{{ table.struct_name }}Window>> {
+        let taos = TDENGINE_POOL.get().await.map_err(taos::Error::from_any)?;
+        let agg = aggregate.as_str();
+        let sql = format!("SELECT _wstart AS window_start, _wend AS window_end{% for v in table.fields %}{% if v.is_tag == false and v.field_type in numbers %}, CAST({agg}(`{{ v.column_name }}`) AS DOUBLE) AS `{{ v.column_name }}`{% endif %}{% endfor %} FROM {TABLE_NAME} WHERE `{{ ts.column_name }}` >= {} AND `{{ ts.column_name }}` < {} INTERVAL({}a)", start.and_utc().{{ts_fn}}, end.and_utc().{{ts_fn}}, interval.as_millis().max(1));
+        let data: Vec<{{ table.struct_name }}Window> = taos.query(&sql).await?.deserialize().try_collect().await?;
+        Ok(data)
+    }
+{% endif -%}
+}
+{% if table.kind == "supertable" %}
+/// Quotes a string as a TDengine SQL literal.
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
 }
-    "#,
-    )
-});
+{% endif %}
+    "#;
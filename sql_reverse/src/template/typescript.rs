@@ -0,0 +1,23 @@
+pub static TYPESCRIPT_TEMPLATE: &str = r#"
+{%- if table.comment -%}
+/** {{ table.comment }} */
+{% endif -%}
+export interface {{ table.struct_name }} {
+{%- for v in table.fields %}
+  {%- if v.comment %}
+  /** {{ v.comment }} */
+  {%- endif %}
+  {{ v.field_name }}: {{ v.field_type }}{% if v.is_null == 1 %} | null{% endif %};
+{%- endfor %}
+}
+
+/** Table and column names of `{{ table.table_name }}`. */
+export const {{ table.struct_name }}Table = {
+  name: '{{ table.table_name | addslashes }}',
+  columns: {
+{%- for v in table.fields %}
+    {{ v.field_name }}: '{{ v.column_name | addslashes }}',
+{%- endfor %}
+  },
+} as const;
+"#;